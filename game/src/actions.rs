@@ -1,235 +1,400 @@
 use bevy::prelude::*;
 
-use crate::level::LoadNextLevel;
+pub use simon_core::actions::{Action, ActionPlan, CWRotation};
+
+use crate::level::{LoadLevel, LoadNextLevel};
 
 pub struct ActionPlugin;
 
 impl Plugin for ActionPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ActionPlan::default())
+            .insert_resource(ActionHistory::default())
             .add_observer(add_action)
             .add_observer(remove_action)
+            .add_observer(insert_action)
+            .add_observer(swap_actions)
+            .add_observer(move_action)
             .add_observer(reset_action_plan)
-            .add_observer(reset_action_plan_on_level_load);
+            .add_observer(load_action_plan)
+            .add_observer(undo)
+            .add_observer(redo)
+            .add_observer(reset_action_plan_on_level_load)
+            .add_observer(clear_history_on_level_select);
     }
 }
 
-#[derive(Debug, Clone, Copy, Event, PartialEq, Eq, Hash, PartialOrd, Ord)]
-pub enum Action {
-    Forward,
-    Right,
-    Backward,
-    Left,
-}
+#[derive(Debug, Clone, Event, Deref)]
+pub struct AddAction(pub Action);
 
-#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
-pub enum CWRotation {
-    #[default]
-    Zero,
-    Ninety,
-    OneEighty,
-    TwoSeventy,
-}
+#[derive(Debug, Clone, Copy, Event, Deref)]
+pub struct RemoveAction(pub usize);
 
-impl CWRotation {
-    pub fn rotate_cw(self) -> Self {
-        match self {
-            CWRotation::Zero => CWRotation::Ninety,
-            CWRotation::Ninety => CWRotation::OneEighty,
-            CWRotation::OneEighty => CWRotation::TwoSeventy,
-            CWRotation::TwoSeventy => CWRotation::Zero,
-        }
-    }
+/// A single reversible edit to the [`ActionPlan`].
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum PlanEdit {
+    Add(Action),
+    Remove {
+        index: usize,
+        action: Action,
+    },
+    Insert {
+        index: usize,
+        action: Action,
+    },
+    Swap(usize, usize),
+    Move {
+        from: usize,
+        to: usize,
+    },
+    Reset(Vec<Action>),
+    Replace {
+        previous: Vec<Action>,
+        next: Vec<Action>,
+    },
+}
 
-    pub fn rotate_ccw(self) -> Self {
+impl PlanEdit {
+    fn apply(&self, action_plan: &mut ActionPlan) {
         match self {
-            CWRotation::Zero => CWRotation::TwoSeventy,
-            CWRotation::Ninety => CWRotation::Zero,
-            CWRotation::OneEighty => CWRotation::Ninety,
-            CWRotation::TwoSeventy => CWRotation::OneEighty,
+            PlanEdit::Add(action) => action_plan.push(*action),
+            PlanEdit::Remove { index, .. } => {
+                action_plan.remove(*index);
+            }
+            PlanEdit::Insert { index, action } => action_plan.insert(*index, *action),
+            PlanEdit::Swap(a, b) => action_plan.swap(*a, *b),
+            PlanEdit::Move { from, to } => {
+                let action = action_plan.remove(*from);
+                action_plan.insert(*to, action);
+            }
+            PlanEdit::Reset(_) => action_plan.clear(),
+            PlanEdit::Replace { next, .. } => action_plan.0.clone_from(next),
         }
     }
 
-    pub fn to_quat(self) -> Quat {
+    fn revert(&self, action_plan: &mut ActionPlan) {
         match self {
-            CWRotation::Zero => Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
-            CWRotation::Ninety => Quat::IDENTITY,
-            CWRotation::OneEighty => Quat::from_rotation_y(-std::f32::consts::FRAC_PI_2),
-            CWRotation::TwoSeventy => Quat::from_rotation_y(-std::f32::consts::PI),
+            PlanEdit::Add(_) => {
+                action_plan.pop();
+            }
+            PlanEdit::Remove { index, action } => action_plan.insert(*index, *action),
+            PlanEdit::Insert { index, .. } => {
+                action_plan.remove(*index);
+            }
+            PlanEdit::Swap(a, b) => action_plan.swap(*a, *b),
+            PlanEdit::Move { from, to } => {
+                let action = action_plan.remove(*to);
+                action_plan.insert(*from, action);
+            }
+            PlanEdit::Reset(previous) | PlanEdit::Replace { previous, .. } => {
+                action_plan.0.clone_from(previous)
+            }
         }
     }
 }
 
-impl CWRotation {
-    pub fn to_combinator(self) -> fn(&Action) -> Action {
-        match self {
-            CWRotation::Zero => |action| *action,
-            CWRotation::Ninety => Action::rotate_cw,
-            CWRotation::OneEighty => Action::rotate_180,
-            CWRotation::TwoSeventy => Action::rotate_ccw,
-        }
-    }
+/// Undo and redo stacks for the edits made to the [`ActionPlan`] on the current level.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ActionHistory {
+    undo: Vec<PlanEdit>,
+    redo: Vec<PlanEdit>,
 }
 
-impl std::fmt::Display for Action {
-    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
-        match self {
-            Action::Forward => write!(f, "↑"),
-            Action::Right => write!(f, "→"),
-            Action::Backward => write!(f, "↓"),
-            Action::Left => write!(f, "←"),
-        }
+impl ActionHistory {
+    pub fn apply(&mut self, edit: PlanEdit, action_plan: &mut ActionPlan) {
+        edit.apply(action_plan);
+        self.undo.push(edit);
+        self.redo.clear();
     }
-}
 
-impl Action {
-    pub fn rotate_cw(&self) -> Self {
-        match self {
-            Action::Forward => Action::Right,
-            Action::Right => Action::Backward,
-            Action::Backward => Action::Left,
-            Action::Left => Action::Forward,
-        }
+    pub fn undo(&mut self, action_plan: &mut ActionPlan) -> bool {
+        let Some(edit) = self.undo.pop() else {
+            return false;
+        };
+
+        edit.revert(action_plan);
+        self.redo.push(edit);
+
+        true
     }
 
-    pub fn rotate_ccw(&self) -> Self {
-        match self {
-            Action::Forward => Action::Left,
-            Action::Right => Action::Forward,
-            Action::Backward => Action::Right,
-            Action::Left => Action::Backward,
-        }
+    pub fn redo(&mut self, action_plan: &mut ActionPlan) -> bool {
+        let Some(edit) = self.redo.pop() else {
+            return false;
+        };
+
+        edit.apply(action_plan);
+        self.undo.push(edit);
+
+        true
     }
 
-    pub fn rotate_180(&self) -> Self {
-        match self {
-            Action::Forward => Action::Backward,
-            Action::Right => Action::Left,
-            Action::Backward => Action::Forward,
-            Action::Left => Action::Right,
-        }
+    pub fn can_undo(&self) -> bool {
+        !self.undo.is_empty()
     }
 
-    pub fn cw_rotation(&self, target: Action) -> CWRotation {
-        match (self, target) {
-            (Action::Forward, Action::Forward) => CWRotation::Zero,
-            (Action::Forward, Action::Right) => CWRotation::Ninety,
-            (Action::Forward, Action::Backward) => CWRotation::OneEighty,
-            (Action::Forward, Action::Left) => CWRotation::TwoSeventy,
-            (Action::Right, Action::Forward) => CWRotation::TwoSeventy,
-            (Action::Right, Action::Right) => CWRotation::Zero,
-            (Action::Right, Action::Backward) => CWRotation::Ninety,
-            (Action::Right, Action::Left) => CWRotation::OneEighty,
-            (Action::Backward, Action::Forward) => CWRotation::OneEighty,
-            (Action::Backward, Action::Right) => CWRotation::TwoSeventy,
-            (Action::Backward, Action::Backward) => CWRotation::Zero,
-            (Action::Backward, Action::Left) => CWRotation::Ninety,
-            (Action::Left, Action::Forward) => CWRotation::Ninety,
-            (Action::Left, Action::Right) => CWRotation::OneEighty,
-            (Action::Left, Action::Backward) => CWRotation::TwoSeventy,
-            (Action::Left, Action::Left) => CWRotation::Zero,
-        }
+    pub fn can_redo(&self) -> bool {
+        !self.redo.is_empty()
     }
-}
 
-impl From<Action> for String {
-    fn from(value: Action) -> Self {
-        match value {
-            Action::Forward => "Forward".into(),
-            Action::Backward => "Backward".into(),
-            Action::Left => "Left".into(),
-            Action::Right => "Right".into(),
-        }
+    pub fn clear(&mut self) {
+        self.undo.clear();
+        self.redo.clear();
     }
 }
 
-#[derive(Debug, Clone, Event, Deref)]
-pub struct AddAction(pub Action);
-
-#[derive(Debug, Clone, Copy, Event, Deref)]
-pub struct RemoveAction(pub usize);
+fn add_action(
+    trigger: Trigger<AddAction>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    history.apply(PlanEdit::Add(**trigger.event()), &mut action_plan);
+}
 
-#[derive(
-    Debug, Default, Clone, Resource, Deref, DerefMut, PartialEq, Eq, Hash, PartialOrd, Ord,
-)]
-pub struct ActionPlan(pub Vec<Action>);
+fn remove_action(
+    trigger: Trigger<RemoveAction>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    let index = **trigger.event();
 
-impl ActionPlan {
-    pub fn phase_iter(&self) -> impl Iterator<Item = ActionPlan> {
-        (1..=self.len()).map(|i| {
-            let mut phase = self.clone();
-            phase.rotate_right(i);
-            phase
-        })
+    if index >= action_plan.len() {
+        tracing::warn!(
+            "attempted to remove action from invalid index: {index}, bounds: [0, {})",
+            action_plan.len()
+        );
+        return;
     }
 
-    pub fn canonicalize_phase(&self) -> Self {
-        self.phase_iter()
-            .map(|plan| plan.canonicalize_rotation().canonicalize_mirror())
-            .min()
-            .unwrap()
-    }
+    let action = action_plan[index];
+    history.apply(PlanEdit::Remove { index, action }, &mut action_plan);
+}
 
-    pub fn mirror(&self) -> Self {
-        ActionPlan(
-            self.iter()
-                .map(|action| match action {
-                    action @ (Action::Forward | Action::Backward) => *action,
-                    Action::Left => Action::Right,
-                    Action::Right => Action::Left,
-                })
-                .collect(),
-        )
-    }
+/// Inserts an action before `index`, an `index` equal to the plan length appends it.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct InsertAction {
+    pub index: usize,
+    pub action: Action,
+}
 
-    pub fn canonicalize_mirror(&self) -> Self {
-        let mirror = self.mirror();
+fn insert_action(
+    trigger: Trigger<InsertAction>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    let InsertAction { index, action } = *trigger.event();
 
-        if self < &mirror { self.clone() } else { mirror }
+    if index > action_plan.len() {
+        tracing::warn!(
+            "attempted to insert action at invalid index: {index}, bounds: [0, {}]",
+            action_plan.len()
+        );
+        return;
     }
 
-    pub fn canonicalize_rotation(&self) -> Self {
-        if self.is_empty() {
-            return Self::default();
-        }
+    history.apply(PlanEdit::Insert { index, action }, &mut action_plan);
+}
 
-        let rotate = self[0].cw_rotation(Action::Forward).to_combinator();
+#[derive(Debug, Clone, Copy, Event)]
+pub struct SwapActions(pub usize, pub usize);
 
-        Self(self.iter().map(rotate).collect())
-    }
+fn swap_actions(
+    trigger: Trigger<SwapActions>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    let SwapActions(a, b) = *trigger.event();
 
-    pub fn canonicalize(&self) -> Self {
-        self.canonicalize_rotation()
-            .canonicalize_mirror()
-            .canonicalize_phase()
+    if a >= action_plan.len() || b >= action_plan.len() {
+        tracing::warn!(
+            "attempted to swap actions at invalid indices: ({a}, {b}), bounds: [0, {})",
+            action_plan.len()
+        );
+        return;
     }
+
+    history.apply(PlanEdit::Swap(a, b), &mut action_plan);
 }
 
-fn add_action(trigger: Trigger<AddAction>, mut action_plan: ResMut<ActionPlan>) {
-    action_plan.push(**trigger.event())
+/// Moves the action at `from` so that it ends up at index `to`, shifting the actions in between.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct MoveAction {
+    pub from: usize,
+    pub to: usize,
 }
 
-fn remove_action(trigger: Trigger<RemoveAction>, mut action_plan: ResMut<ActionPlan>) {
-    let index = **trigger.event();
+fn move_action(
+    trigger: Trigger<MoveAction>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    let MoveAction { from, to } = *trigger.event();
 
-    if index > action_plan.len() {
+    if from >= action_plan.len() || to >= action_plan.len() {
         tracing::warn!(
-            "attempted to remove action from invalid index: {index}, bounds: [0, {})",
+            "attempted to move action between invalid indices: ({from}, {to}), bounds: [0, {})",
             action_plan.len()
         );
         return;
     }
 
-    action_plan.remove(index);
+    if from == to {
+        return;
+    }
+
+    history.apply(PlanEdit::Move { from, to }, &mut action_plan);
 }
 
 #[derive(Debug, Clone, Copy, Event)]
 pub struct ResetActionPlan;
 
-fn reset_action_plan(_trigger: Trigger<ResetActionPlan>, mut action_plan: ResMut<ActionPlan>) {
+fn reset_action_plan(
+    _trigger: Trigger<ResetActionPlan>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    if action_plan.is_empty() {
+        return;
+    }
+
+    let previous = action_plan.0.clone();
+    history.apply(PlanEdit::Reset(previous), &mut action_plan);
+}
+
+/// Replaces the whole plan, e.g. with one loaded from a share code.
+#[derive(Debug, Clone, Event)]
+pub struct LoadActionPlan(pub ActionPlan);
+
+fn load_action_plan(
+    trigger: Trigger<LoadActionPlan>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    let next = trigger.event().0.0.clone();
+
+    if action_plan.0 == next {
+        return;
+    }
+
+    let previous = action_plan.0.clone();
+    history.apply(PlanEdit::Replace { previous, next }, &mut action_plan);
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct Undo;
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct Redo;
+
+fn undo(
+    _trigger: Trigger<Undo>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    history.undo(&mut action_plan);
+}
+
+fn redo(
+    _trigger: Trigger<Redo>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
+    history.redo(&mut action_plan);
+}
+
+fn reset_action_plan_on_level_load(
+    _trigger: Trigger<LoadNextLevel>,
+    mut action_plan: ResMut<ActionPlan>,
+    mut history: ResMut<ActionHistory>,
+) {
     action_plan.clear();
+    history.clear();
 }
 
-fn reset_action_plan_on_level_load(_trigger: Trigger<LoadNextLevel>, mut commands: Commands) {
-    commands.trigger(ResetActionPlan);
+fn clear_history_on_level_select(_trigger: Trigger<LoadLevel>, mut history: ResMut<ActionHistory>) {
+    history.clear();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use Action::*;
+
+    #[test]
+    fn undo_redo_round_trip() {
+        let mut plan = ActionPlan::default();
+        let mut history = ActionHistory::default();
+
+        history.apply(PlanEdit::Add(Forward), &mut plan);
+        history.apply(PlanEdit::Add(Right), &mut plan);
+        history.apply(PlanEdit::Add(Left), &mut plan);
+        history.apply(PlanEdit::Swap(0, 2), &mut plan);
+        history.apply(
+            PlanEdit::Remove {
+                index: 1,
+                action: Right,
+            },
+            &mut plan,
+        );
+        history.apply(PlanEdit::Reset(plan.0.clone()), &mut plan);
+
+        assert_eq!(plan, ActionPlan(vec![]));
+
+        assert!(history.undo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![Left, Forward]));
+
+        assert!(history.undo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![Left, Right, Forward]));
+
+        assert!(history.undo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![Forward, Right, Left]));
+
+        assert!(history.redo(&mut plan));
+        assert!(history.redo(&mut plan));
+        assert!(history.redo(&mut plan));
+        assert!(!history.redo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![]));
+    }
+
+    #[test]
+    fn insert_and_move_round_trip() {
+        let mut plan = ActionPlan(vec![Forward, Right, Backward]);
+        let mut history = ActionHistory::default();
+
+        history.apply(
+            PlanEdit::Insert {
+                index: 1,
+                action: Left,
+            },
+            &mut plan,
+        );
+        assert_eq!(plan, ActionPlan(vec![Forward, Left, Right, Backward]));
+
+        history.apply(PlanEdit::Move { from: 0, to: 3 }, &mut plan);
+        assert_eq!(plan, ActionPlan(vec![Left, Right, Backward, Forward]));
+
+        history.apply(PlanEdit::Move { from: 2, to: 0 }, &mut plan);
+        assert_eq!(plan, ActionPlan(vec![Backward, Left, Right, Forward]));
+
+        assert!(history.undo(&mut plan));
+        assert!(history.undo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![Forward, Left, Right, Backward]));
+
+        assert!(history.undo(&mut plan));
+        assert_eq!(plan, ActionPlan(vec![Forward, Right, Backward]));
+    }
+
+    #[test]
+    fn new_edit_discards_redo() {
+        let mut plan = ActionPlan::default();
+        let mut history = ActionHistory::default();
+
+        history.apply(PlanEdit::Add(Forward), &mut plan);
+        history.undo(&mut plan);
+        history.apply(PlanEdit::Add(Backward), &mut plan);
+
+        assert!(!history.can_redo());
+        assert_eq!(plan, ActionPlan(vec![Backward]));
+    }
 }
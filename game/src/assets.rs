@@ -105,6 +105,9 @@ pub struct IconAssets {
     #[asset(path = "icons/down.png")]
     pub down: Handle<Image>,
 
+    #[asset(path = "icons/drag.png")]
+    pub drag: Handle<Image>,
+
     #[asset(path = "icons/bars.png")]
     pub bars: Handle<Image>,
 
@@ -113,4 +116,7 @@ pub struct IconAssets {
 
     #[asset(path = "icons/unmute.png")]
     pub unmute: Handle<Image>,
+
+    #[asset(path = "icons/lock.png")]
+    pub lock: Handle<Image>,
 }
@@ -0,0 +1,378 @@
+//! Keeps the level in view, framing its tiles in the part of the screen the UI leaves uncovered,
+//! and lets the player orbit, pan and zoom around it.
+
+use std::{
+    f32::consts::{PI, TAU},
+    time::Duration,
+};
+
+use bevy::{
+    input::mouse::{AccumulatedMouseMotion, AccumulatedMouseScroll, MouseScrollUnit},
+    prelude::*,
+    window::PrimaryWindow,
+};
+use bevy_tweening::{Animator, Lens, Tween, component_animator_system};
+
+use crate::{
+    game_state::GameState,
+    level::Level,
+    ui::{UiRoot, button, schematic::ViewMode},
+};
+
+pub struct CameraPlugin;
+
+impl Plugin for CameraPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<Framing>()
+            .add_systems(Update, component_animator_system::<LevelCamera>)
+            .add_systems(
+                Update,
+                (
+                    (
+                        frame_level,
+                        camera_controls.run_if(resource_equals(ViewMode::Perspective)),
+                    )
+                        .run_if(in_state(GameState::InGame)),
+                    place_camera,
+                )
+                    .chain(),
+            )
+            .add_observer(reset_view);
+    }
+}
+
+impl CameraPlugin {
+    pub fn reset_view_button() -> impl Bundle {
+        (
+            Name::new("Reset View Button"),
+            button::Button::builder()
+                .text("Reset view".into())
+                .on_click(|commands| commands.trigger(ResetView))
+                .build(),
+        )
+    }
+}
+
+/// The direction the camera looks at the level from.
+const VIEW_DIRECTION: Vec3 = Vec3::new(-3., 5.5, 9.);
+
+/// Levels smaller than this are framed as if they were this big, so they don't fill the screen.
+const MIN_RADIUS: f32 = 3.;
+
+/// Space left around the tiles, in tiles.
+const MARGIN: f32 = 0.5;
+
+/// Radians per pixel dragged.
+const ORBIT_SPEED: f32 = 0.005;
+/// Multiples of the distance per pixel dragged.
+const PAN_SPEED: f32 = 0.0015;
+/// How much closer each line scrolled moves the camera.
+const ZOOM_SPEED: f32 = 1.1;
+
+const MIN_ELEVATION: f32 = 0.15;
+const MAX_ELEVATION: f32 = 1.45;
+const MIN_DISTANCE: f32 = 3.;
+const MAX_DISTANCE: f32 = 60.;
+
+/// Where the camera orbits and how far back it sits, its [`Transform`] follows this.
+#[derive(Debug, Clone, Copy, PartialEq, Component)]
+pub struct LevelCamera {
+    pub focus: Vec3,
+    pub distance: f32,
+    /// Angle around the vertical axis, zero is looking towards negative z.
+    pub yaw: f32,
+    /// Angle above the ground.
+    pub elevation: f32,
+    /// How far the camera is moved left so `focus` sits right of the middle of the screen, as a
+    /// multiple of the distance.
+    pub lateral: f32,
+}
+
+impl Default for LevelCamera {
+    fn default() -> Self {
+        let ground = Vec2::new(VIEW_DIRECTION.x, VIEW_DIRECTION.z);
+
+        Self {
+            focus: Vec3::Y,
+            distance: VIEW_DIRECTION.length(),
+            yaw: ground.x.atan2(ground.y),
+            elevation: VIEW_DIRECTION.y.atan2(ground.length()),
+            lateral: 0.,
+        }
+    }
+}
+
+impl LevelCamera {
+    pub fn transform(&self) -> Transform {
+        let direction = Vec3::new(
+            self.elevation.cos() * self.yaw.sin(),
+            self.elevation.sin(),
+            self.elevation.cos() * self.yaw.cos(),
+        );
+
+        let mut transform = Transform::from_translation(self.focus + direction * self.distance)
+            .looking_at(self.focus, Vec3::Y);
+        transform.translation -= transform.right() * self.lateral * self.distance;
+        transform
+    }
+
+    /// Fits the tiles of `level` in the view, `covered` is the fraction of the screen width hidden
+    /// behind the UI on the left.
+    pub fn framing(level: &Level, fov: f32, aspect_ratio: f32, covered: f32) -> Self {
+        let (min, max) = level.tiles.keys().fold(
+            (Vec2::splat(f32::MAX), Vec2::splat(f32::MIN)),
+            |(min, max), &(x, y)| {
+                let position = Vec2::new(x as f32, y as f32);
+                (min.min(position), max.max(position))
+            },
+        );
+
+        if min.x > max.x {
+            return Self::default();
+        }
+
+        let centre = (min + max) / 2.;
+        let radius =
+            ((max - min) / 2. + 0.5 + MARGIN).length().max(MIN_RADIUS) * level.zoom.unwrap_or(1.);
+
+        let vertical = (fov / 2.).tan();
+        let horizontal = vertical * aspect_ratio;
+        let distance = radius / vertical.min(horizontal * (1. - covered)).atan().sin();
+
+        Self {
+            focus: Vec3::new(centre.x, 0., centre.y),
+            distance,
+            // Moving left of the level puts it in the middle of the uncovered part of the screen
+            lateral: covered * horizontal,
+            ..default()
+        }
+    }
+
+    fn is_near(&self, other: &Self) -> bool {
+        self.focus.distance(other.focus) < 0.01
+            && (self.distance - other.distance).abs() < 0.01
+            && (self.lateral - other.lateral).abs() < 0.001
+    }
+}
+
+#[derive(Debug)]
+pub struct LevelCameraLens {
+    start: LevelCamera,
+    end: LevelCamera,
+}
+
+impl LevelCameraLens {
+    /// Turns the short way round to `end`, however many times the player has orbited.
+    pub fn new(start: LevelCamera, mut end: LevelCamera) -> Self {
+        end.yaw = start.yaw + (end.yaw - start.yaw + PI).rem_euclid(TAU) - PI;
+        Self { start, end }
+    }
+}
+
+impl Lens<LevelCamera> for LevelCameraLens {
+    fn lerp(&mut self, target: &mut dyn bevy_tweening::Targetable<LevelCamera>, ratio: f32) {
+        target.focus = self.start.focus.lerp(self.end.focus, ratio);
+        target.distance = self.start.distance.lerp(self.end.distance, ratio);
+        target.yaw = self.start.yaw.lerp(self.end.yaw, ratio);
+        target.elevation = self.start.elevation.lerp(self.end.elevation, ratio);
+        target.lateral = self.start.lateral.lerp(self.end.lateral, ratio);
+    }
+}
+
+/// The framing the camera last tweened to, cleared to tween back to it.
+#[derive(Debug, Default, Resource)]
+struct Framing(Option<LevelCamera>);
+
+/// Undoes the player's orbiting, panning and zooming.
+#[derive(Debug, Event)]
+pub struct ResetView;
+
+fn reset_view(_trigger: Trigger<ResetView>, mut framing: ResMut<Framing>) {
+    framing.0 = None;
+}
+
+/// The fraction of the window's width the UI columns reach across.
+pub(crate) fn covered_width(
+    window: &Window,
+    ui_root: &Query<&Children, With<UiRoot>>,
+    nodes: &Query<(&ComputedNode, &GlobalTransform)>,
+) -> f32 {
+    let right_edge = ui_root
+        .iter()
+        .flat_map(|children| nodes.iter_many(children))
+        .map(|(node, transform)| transform.translation().x + node.size().x / 2.)
+        .fold(0., f32::max);
+
+    (right_edge / window.physical_width().max(1) as f32).clamp(0., 0.9)
+}
+
+fn frame_level(
+    mut commands: Commands,
+    level: Res<Level>,
+    camera: Query<(Entity, &LevelCamera, &Projection)>,
+    window: Query<&Window, With<PrimaryWindow>>,
+    ui_root: Query<&Children, With<UiRoot>>,
+    nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    mut target: ResMut<Framing>,
+) -> Result {
+    let (entity, current, projection) = camera.single()?;
+    let Projection::Perspective(perspective) = projection else {
+        return Ok(());
+    };
+
+    let framing = LevelCamera::framing(
+        &level,
+        perspective.fov,
+        perspective.aspect_ratio,
+        covered_width(window.single()?, &ui_root, &nodes),
+    );
+
+    // Window resizes and the UI settling move the target a little at a time, only new targets tween
+    if target.0.is_some_and(|target| target.is_near(&framing)) {
+        return Ok(());
+    }
+
+    target.0 = Some(framing);
+
+    commands.entity(entity).insert(Animator::new(Tween::new(
+        EaseFunction::QuadraticInOut,
+        Duration::from_secs_f32(1.5),
+        LevelCameraLens::new(*current, framing),
+    )));
+
+    Ok(())
+}
+
+/// Left drag orbits, right drag pans and scrolling zooms. Drags only start away from the UI.
+fn camera_controls(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    motion: Res<AccumulatedMouseMotion>,
+    scroll: Res<AccumulatedMouseScroll>,
+    interactions: Query<&Interaction>,
+    mut camera: Query<(Entity, &mut LevelCamera)>,
+    mut drag: Local<Option<MouseButton>>,
+) -> Result {
+    let over_ui = interactions
+        .iter()
+        .any(|interaction| *interaction != Interaction::None);
+
+    if drag.is_some_and(|button| !mouse.pressed(button)) {
+        *drag = None;
+    }
+
+    if drag.is_none() && !over_ui {
+        *drag = [MouseButton::Left, MouseButton::Right]
+            .into_iter()
+            .find(|button| mouse.just_pressed(*button));
+    }
+
+    let scrolled = match scroll.unit {
+        _ if over_ui => 0.,
+        MouseScrollUnit::Line => scroll.delta.y,
+        MouseScrollUnit::Pixel => scroll.delta.y / 100.,
+    };
+    let dragged = drag.is_some() && motion.delta != Vec2::ZERO;
+
+    if !dragged && scrolled == 0. {
+        return Ok(());
+    }
+
+    let (entity, mut camera) = camera.single_mut()?;
+
+    // The player takes over from any framing still under way
+    commands.entity(entity).remove::<Animator<LevelCamera>>();
+
+    match *drag {
+        Some(MouseButton::Left) => {
+            camera.yaw -= motion.delta.x * ORBIT_SPEED;
+            camera.elevation = (camera.elevation + motion.delta.y * ORBIT_SPEED)
+                .clamp(MIN_ELEVATION, MAX_ELEVATION);
+        }
+        Some(MouseButton::Right) => {
+            let transform = camera.transform();
+            let right = transform.right().with_y(0.).normalize_or_zero();
+            let forward = transform.forward().with_y(0.).normalize_or_zero();
+            let distance = camera.distance;
+
+            camera.focus -=
+                (right * motion.delta.x - forward * motion.delta.y) * PAN_SPEED * distance;
+        }
+        _ => {}
+    }
+
+    if scrolled != 0. {
+        camera.distance =
+            (camera.distance * ZOOM_SPEED.powf(-scrolled)).clamp(MIN_DISTANCE, MAX_DISTANCE);
+    }
+
+    Ok(())
+}
+
+fn place_camera(mut camera: Query<(&LevelCamera, &mut Transform), Changed<LevelCamera>>) {
+    for (camera, mut transform) in &mut camera {
+        *transform = camera.transform();
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::f32::consts::FRAC_PI_4;
+
+    use super::*;
+
+    use crate::level::{SCENES, Scene};
+
+    fn level(name: &str) -> &'static Level {
+        SCENES
+            .iter()
+            .find_map(|scene| match scene {
+                Scene::Level(level) if level.name == name => Some(level),
+                _ => None,
+            })
+            .unwrap()
+    }
+
+    #[test]
+    fn bigger_levels_are_framed_from_further_away() {
+        let small = LevelCamera::framing(level("Obstructions"), FRAC_PI_4, 16. / 9., 0.);
+        let big = LevelCamera::framing(level("Blizzard"), FRAC_PI_4, 16. / 9., 0.);
+
+        assert!(big.distance > small.distance);
+        assert_eq!(small.focus, Vec3::ZERO);
+    }
+
+    #[test]
+    fn covered_screens_look_past_the_level() {
+        let open = LevelCamera::framing(level("Rift"), FRAC_PI_4, 16. / 9., 0.);
+        let covered = LevelCamera::framing(level("Rift"), FRAC_PI_4, 16. / 9., 0.4);
+
+        assert!(covered.distance >= open.distance);
+        assert_eq!(covered.focus, open.focus);
+
+        let right = LevelCamera::default().transform().right();
+        let shift = covered.transform().translation - open.transform().translation;
+        assert!(shift.dot(*right) < 0.);
+    }
+
+    #[test]
+    fn default_matches_the_fixed_camera() {
+        let transform = LevelCamera::default().transform();
+
+        assert!(transform.translation.distance(Vec3::new(-3., 6.5, 9.)) < 1e-4);
+        assert!(transform.forward().dot(-VIEW_DIRECTION.normalize()) > 0.9999);
+    }
+
+    #[test]
+    fn resetting_turns_the_short_way_round() {
+        let start = LevelCamera {
+            yaw: 3. * TAU + 0.1,
+            ..default()
+        };
+        let lens = LevelCameraLens::new(start, LevelCamera::default());
+
+        assert!((lens.end.yaw - start.yaw).abs() <= PI);
+        let turns = (lens.end.yaw - LevelCamera::default().yaw) / TAU;
+        assert!((turns - turns.round()).abs() < 1e-4);
+    }
+}
@@ -0,0 +1,33 @@
+//! Access to the system clipboard where the platform gives us one.
+//!
+//! The browser clipboard API is async and permission gated, so the web build goes without and the
+//! UI falls back to a text input instead.
+
+/// Whether [`read`] and [`write`] can ever succeed on this platform.
+pub const AVAILABLE: bool = cfg!(not(target_arch = "wasm32"));
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn read() -> Option<String> {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.get_text())
+        .inspect_err(|error| tracing::warn!(?error, "failed to read clipboard"))
+        .ok()
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn write(text: &str) -> bool {
+    arboard::Clipboard::new()
+        .and_then(|mut clipboard| clipboard.set_text(text))
+        .inspect_err(|error| tracing::warn!(?error, "failed to write clipboard"))
+        .is_ok()
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn read() -> Option<String> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn write(_text: &str) -> bool {
+    false
+}
@@ -204,6 +204,7 @@ fn eye_track_player(
 fn animate_eye_direction(
     mut eye: Query<(&mut Transform, &Eye, &Emotion, &Children), Without<Iris>>,
     mut iris: Query<&mut Transform, With<Iris>>,
+    camera: Query<&Transform, (With<Camera>, Without<Eye>, Without<Iris>)>,
     time: Res<Time>,
     #[cfg(feature = "debug")] mut gizmos: Gizmos,
 ) {
@@ -262,9 +263,15 @@ fn animate_eye_direction(
             );
         }
 
-        if !matches!(emotion, Emotion::Neutral { .. }) {
+        // Resting eyes turn back to face the camera, which the player can move
+        let facing = match emotion {
+            Emotion::Neutral { .. } => camera.single().ok().map(|camera| camera.translation),
+            _ => Some(eye.target),
+        };
+
+        if let Some(facing) = facing {
             let rotation = transform.rotation.lerp(
-                transform.looking_at(eye.target, Vec3::Y).rotation * base_rotation,
+                transform.looking_at(facing, Vec3::Y).rotation * base_rotation,
                 0.5 * time.delta_secs(),
             );
 
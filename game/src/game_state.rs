@@ -1,16 +1,18 @@
 use bevy::prelude::*;
 use bevy_pkv::PkvStore;
 
-use crate::ui::challenges::ChallengeState;
+use crate::{
+    save::{STORE_NAME, STORE_ORGANIZATION},
+    ui::challenges::ChallengeState,
+};
 
 pub struct GameStatePlugin;
 
 impl Plugin for GameStatePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(ChallengeState::new())
-            .insert_resource(PkvStore::new("DylanRJohnston", "SimonSaysV2"))
-            .add_systems(Startup, setup)
-            .add_systems(Update, save_state)
+            // Changes to the save format go through a migration in `save`, never a new store name
+            .insert_resource(PkvStore::new(STORE_ORGANIZATION, STORE_NAME))
             .add_observer(reset_challenge_state);
     }
 }
@@ -33,20 +35,12 @@ pub enum LevelState {
     Unloading,
 }
 
-fn setup(pkv: Res<PkvStore>, mut state: ResMut<ChallengeState>) {
-    if let Ok(from_storage) = pkv.get::<ChallengeState>("challenge_state") {
-        *state = from_storage;
-    }
-}
-
-fn save_state(mut pkv: ResMut<PkvStore>, state: Res<ChallengeState>) {
-    if !state.is_changed() {
-        return;
-    }
-
-    if let Err(err) = pkv.set("challenge_state", &*state) {
-        tracing::error!(?err, "failed to save challenge state");
-    }
+/// Seconds since the Unix epoch, for timestamps in the save.
+pub fn unix_timestamp() -> u64 {
+    web_time::SystemTime::now()
+        .duration_since(web_time::UNIX_EPOCH)
+        .map(|duration| duration.as_secs())
+        .unwrap_or_default()
 }
 
 #[derive(Debug, Clone, Copy, Event)]
@@ -0,0 +1,498 @@
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{
+        Action, ActionPlan, AddAction, Redo, RemoveAction, ResetActionPlan, SwapActions, Undo,
+    },
+    game_state::GameState,
+    level::Level,
+    replay::ReplayMode,
+    simulation::{SimulationStart, SimulationState},
+    ui::{
+        action_list::ActionCursor,
+        focus::{ActivateFocus, MoveFocus},
+        schematic::ToggleViewMode,
+        settings::{CreateSettingsUI, DestroySettingsUI, SelectLevel, level_grid_scene},
+        share::{CopyShareCode, PasteShareCode, SharePanelRoot},
+        text_input::{TextInputFocus, edit_text_input},
+    },
+};
+
+pub struct InputMapPlugin;
+
+impl Plugin for InputMapPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(InputMap::default())
+            .add_systems(
+                Update,
+                (
+                    dispatch_keyboard_input.after(edit_text_input),
+                    dispatch_gamepad_input,
+                )
+                    .run_if(not(in_state(GameState::Loading))),
+            )
+            .add_systems(
+                Update,
+                gamepad_menu_navigation.run_if(not(in_state(GameState::Loading))),
+            )
+            .add_observer(perform_input_action);
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Event, Serialize, Deserialize)]
+pub enum InputAction {
+    AddAction(Action),
+    RemoveLast,
+    Start,
+    Clear,
+    Undo,
+    Redo,
+    ToggleSettings,
+    /// Moves the [`ActionCursor`] towards the start of the plan.
+    CursorUp,
+    /// Moves the [`ActionCursor`] towards the end of the plan.
+    CursorDown,
+    /// Swaps the action under the cursor with the one before it.
+    MoveActionUp,
+    /// Swaps the action under the cursor with the one after it.
+    MoveActionDown,
+    RemoveAtCursor,
+    /// Selects the nth (zero based) card in the settings panel's level grid.
+    SelectLevel(usize),
+    CopyShareCode,
+    PasteShareCode,
+    /// Switches between the perspective and schematic views.
+    ToggleView,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Serialize, Deserialize)]
+pub struct KeyChord {
+    pub key: KeyCode,
+    #[serde(default)]
+    pub ctrl: bool,
+    #[serde(default)]
+    pub shift: bool,
+}
+
+impl KeyChord {
+    pub const fn key(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: false,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: true,
+            shift: false,
+        }
+    }
+
+    pub const fn ctrl_shift(key: KeyCode) -> Self {
+        Self {
+            key,
+            ctrl: true,
+            shift: true,
+        }
+    }
+
+    fn just_pressed(&self, keys: &ButtonInput<KeyCode>) -> bool {
+        // Command is accepted in place of control so the usual macOS shortcuts work
+        let ctrl = keys.any_pressed([
+            KeyCode::ControlLeft,
+            KeyCode::ControlRight,
+            KeyCode::SuperLeft,
+            KeyCode::SuperRight,
+        ]);
+        let shift = keys.any_pressed([KeyCode::ShiftLeft, KeyCode::ShiftRight]);
+
+        keys.just_pressed(self.key) && self.ctrl == ctrl && self.shift == shift
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Binding {
+    pub chord: KeyChord,
+    pub action: InputAction,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub struct GamepadBinding {
+    pub button: GamepadButton,
+    pub action: InputAction,
+}
+
+/// Keyboard and gamepad bindings, saved with each profile so players can remap them.
+///
+/// Bindings are stored as a list rather than a map so the saved form stays valid JSON on wasm.
+#[derive(Debug, Clone, Resource, PartialEq, Eq, Serialize, Deserialize)]
+pub struct InputMap {
+    pub bindings: Vec<Binding>,
+    #[serde(default = "default_gamepad_bindings")]
+    pub gamepad: Vec<GamepadBinding>,
+}
+
+fn default_gamepad_bindings() -> Vec<GamepadBinding> {
+    use GamepadButton::*;
+
+    [
+        (DPadUp, InputAction::AddAction(Action::Forward)),
+        (DPadRight, InputAction::AddAction(Action::Right)),
+        (DPadDown, InputAction::AddAction(Action::Backward)),
+        (DPadLeft, InputAction::AddAction(Action::Left)),
+        (South, InputAction::Start),
+        (East, InputAction::Clear),
+        (West, InputAction::Undo),
+        (North, InputAction::Redo),
+        (LeftTrigger, InputAction::CursorUp),
+        (RightTrigger, InputAction::CursorDown),
+        (LeftTrigger2, InputAction::MoveActionUp),
+        (RightTrigger2, InputAction::MoveActionDown),
+        (Select, InputAction::RemoveAtCursor),
+        (Start, InputAction::ToggleSettings),
+    ]
+    .into_iter()
+    .map(|(button, action)| GamepadBinding { button, action })
+    .collect()
+}
+
+impl Default for InputMap {
+    fn default() -> Self {
+        use KeyCode::*;
+
+        let mut bindings = vec![
+            (
+                KeyChord::key(ArrowUp),
+                InputAction::AddAction(Action::Forward),
+            ),
+            (
+                KeyChord::key(ArrowRight),
+                InputAction::AddAction(Action::Right),
+            ),
+            (
+                KeyChord::key(ArrowDown),
+                InputAction::AddAction(Action::Backward),
+            ),
+            (
+                KeyChord::key(ArrowLeft),
+                InputAction::AddAction(Action::Left),
+            ),
+            (KeyChord::key(KeyW), InputAction::AddAction(Action::Forward)),
+            (KeyChord::key(KeyD), InputAction::AddAction(Action::Right)),
+            (
+                KeyChord::key(KeyS),
+                InputAction::AddAction(Action::Backward),
+            ),
+            (KeyChord::key(KeyA), InputAction::AddAction(Action::Left)),
+            (KeyChord::key(Backspace), InputAction::RemoveLast),
+            (KeyChord::key(Enter), InputAction::Start),
+            (KeyChord::key(NumpadEnter), InputAction::Start),
+            (KeyChord::key(Escape), InputAction::ToggleSettings),
+            (KeyChord::ctrl(KeyZ), InputAction::Undo),
+            (KeyChord::ctrl_shift(KeyZ), InputAction::Redo),
+            (KeyChord::ctrl(KeyC), InputAction::CopyShareCode),
+            (KeyChord::ctrl(KeyV), InputAction::PasteShareCode),
+            (KeyChord::key(KeyV), InputAction::ToggleView),
+        ];
+
+        bindings.extend(
+            [
+                Digit1, Digit2, Digit3, Digit4, Digit5, Digit6, Digit7, Digit8, Digit9, Digit0,
+            ]
+            .into_iter()
+            .enumerate()
+            .map(|(index, key)| (KeyChord::key(key), InputAction::SelectLevel(index))),
+        );
+
+        Self {
+            bindings: bindings
+                .into_iter()
+                .map(|(chord, action)| Binding { chord, action })
+                .collect(),
+            gamepad: default_gamepad_bindings(),
+        }
+    }
+}
+
+impl InputMap {
+    /// Adds a binding, replacing whatever the chord was previously bound to.
+    pub fn bind(&mut self, chord: KeyChord, action: InputAction) {
+        self.bindings.retain(|binding| binding.chord != chord);
+        self.bindings.push(Binding { chord, action });
+    }
+
+    /// Removes every binding for the given action.
+    pub fn unbind(&mut self, action: InputAction) {
+        self.bindings.retain(|binding| binding.action != action);
+    }
+
+    pub fn chords(&self, action: InputAction) -> impl Iterator<Item = KeyChord> + '_ {
+        self.bindings
+            .iter()
+            .filter(move |binding| binding.action == action)
+            .map(|binding| binding.chord)
+    }
+
+    /// Binds a gamepad button, replacing whatever it was previously bound to.
+    pub fn bind_gamepad(&mut self, button: GamepadButton, action: InputAction) {
+        self.gamepad.retain(|binding| binding.button != button);
+        self.gamepad.push(GamepadBinding { button, action });
+    }
+
+    /// Binds the default keys for any action the map doesn't mention, so actions added since the
+    /// map was saved still get bound.
+    pub fn add_missing_defaults(&mut self) {
+        let defaults = InputMap::default();
+
+        for binding in defaults.bindings {
+            if !self.bindings.iter().any(|b| b.action == binding.action) {
+                self.bindings.push(binding);
+            }
+        }
+
+        for binding in defaults.gamepad {
+            if !self.gamepad.iter().any(|b| b.action == binding.action) {
+                self.gamepad.push(binding);
+            }
+        }
+    }
+
+    pub fn just_pressed<'a>(
+        &'a self,
+        keys: &'a ButtonInput<KeyCode>,
+    ) -> impl Iterator<Item = InputAction> + 'a {
+        self.bindings
+            .iter()
+            .filter(|binding| binding.chord.just_pressed(keys))
+            .map(|binding| binding.action)
+    }
+
+    pub fn gamepad_just_pressed<'a>(
+        &'a self,
+        gamepad: &'a Gamepad,
+    ) -> impl Iterator<Item = InputAction> + 'a {
+        self.gamepad
+            .iter()
+            .filter(|binding| gamepad.just_pressed(binding.button))
+            .map(|binding| binding.action)
+    }
+}
+
+fn dispatch_keyboard_input(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    input_map: Res<InputMap>,
+    text_input_focus: Res<TextInputFocus>,
+    share_panel: Query<(), With<SharePanelRoot>>,
+) {
+    // Keys belong to the text input while one is being typed in, and the share panel keeps the
+    // plan underneath it from being edited
+    if text_input_focus.is_some() || !share_panel.is_empty() {
+        return;
+    }
+
+    for input in input_map.just_pressed(&keys) {
+        commands.trigger(input);
+    }
+}
+
+fn dispatch_gamepad_input(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    input_map: Res<InputMap>,
+    game_state: Res<State<GameState>>,
+) {
+    // Outside of a level the face buttons and d-pad drive the menu focus instead
+    let in_game = *game_state == GameState::InGame;
+
+    for gamepad in &gamepads {
+        for input in input_map.gamepad_just_pressed(gamepad) {
+            if in_game || input == InputAction::ToggleSettings {
+                commands.trigger(input);
+            }
+        }
+    }
+}
+
+const STICK_DEADZONE: f32 = 0.5;
+
+/// In a level the d-pad and face buttons edit the plan, so only the stick moves focus there and
+/// clicking it in presses the focused button.
+fn gamepad_menu_navigation(
+    mut commands: Commands,
+    gamepads: Query<&Gamepad>,
+    game_state: Res<State<GameState>>,
+    mut stick_direction: Local<Option<MoveFocus>>,
+) {
+    let in_game = *game_state == GameState::InGame;
+
+    for gamepad in &gamepads {
+        let dpad = [
+            (GamepadButton::DPadUp, MoveFocus::Up),
+            (GamepadButton::DPadDown, MoveFocus::Down),
+            (GamepadButton::DPadLeft, MoveFocus::Left),
+            (GamepadButton::DPadRight, MoveFocus::Right),
+        ];
+
+        for (button, direction) in dpad {
+            if !in_game && gamepad.just_pressed(button) {
+                commands.trigger(direction);
+            }
+        }
+
+        // The stick only moves focus when it's pushed out of the deadzone, not while it's held
+        let stick = gamepad.left_stick();
+        let direction = if stick.length() < STICK_DEADZONE {
+            None
+        } else if stick.x.abs() > stick.y.abs() {
+            Some(if stick.x > 0. {
+                MoveFocus::Right
+            } else {
+                MoveFocus::Left
+            })
+        } else {
+            // Stick y points up, unlike UI space
+            Some(if stick.y > 0. {
+                MoveFocus::Up
+            } else {
+                MoveFocus::Down
+            })
+        };
+
+        if direction != *stick_direction {
+            if let Some(direction) = direction {
+                commands.trigger(direction);
+            }
+            *stick_direction = direction;
+        }
+
+        let activate = match in_game {
+            true => GamepadButton::LeftThumb,
+            false => GamepadButton::South,
+        };
+
+        if gamepad.just_pressed(activate) {
+            commands.trigger(ActivateFocus);
+        }
+
+        if gamepad.just_pressed(GamepadButton::East) && *game_state == GameState::Paused {
+            commands.trigger(DestroySettingsUI);
+        }
+    }
+}
+
+fn perform_input_action(
+    trigger: Trigger<InputAction>,
+    mut commands: Commands,
+    game_state: Res<State<GameState>>,
+    simulation_state: Res<State<SimulationState>>,
+    action_plan: Res<ActionPlan>,
+    level: Res<Level>,
+    replay: Res<ReplayMode>,
+    mut cursor: ResMut<ActionCursor>,
+) {
+    let in_game = *game_state == GameState::InGame;
+    let editable = in_game && *simulation_state == SimulationState::Stopped && !replay.is_active();
+
+    match *trigger.event() {
+        InputAction::AddAction(action) => {
+            if editable && level.actions.contains(&action) && action_plan.len() < level.action_limit
+            {
+                commands.trigger(AddAction(action));
+            }
+        }
+        InputAction::RemoveLast => {
+            if editable && !action_plan.is_empty() {
+                commands.trigger(RemoveAction(action_plan.len() - 1));
+            }
+        }
+        InputAction::Start => {
+            if editable && !action_plan.is_empty() {
+                commands.trigger(SimulationStart);
+            }
+        }
+        InputAction::Clear => {
+            if editable {
+                commands.trigger(ResetActionPlan);
+            }
+        }
+        InputAction::Undo => {
+            if editable {
+                commands.trigger(Undo);
+            }
+        }
+        InputAction::Redo => {
+            if editable {
+                commands.trigger(Redo);
+            }
+        }
+        InputAction::ToggleSettings => match game_state.get() {
+            GameState::InGame => commands.trigger(CreateSettingsUI),
+            GameState::Paused => commands.trigger(DestroySettingsUI),
+            _ => {}
+        },
+        InputAction::SelectLevel(n) => {
+            if *game_state != GameState::Paused {
+                return;
+            }
+
+            if let Some(level_id) = level_grid_scene(n) {
+                commands.trigger(SelectLevel(level_id));
+            }
+        }
+        InputAction::CursorUp => {
+            if editable && !action_plan.is_empty() {
+                **cursor = Some(match **cursor {
+                    Some(index) => index.saturating_sub(1),
+                    None => action_plan.len() - 1,
+                });
+            }
+        }
+        InputAction::CursorDown => {
+            if editable && !action_plan.is_empty() {
+                **cursor = Some(match **cursor {
+                    Some(index) => (index + 1).min(action_plan.len() - 1),
+                    None => 0,
+                });
+            }
+        }
+        InputAction::MoveActionUp => {
+            if let (true, Some(index @ 1..)) = (editable, **cursor) {
+                commands.trigger(SwapActions(index, index - 1));
+                **cursor = Some(index - 1);
+            }
+        }
+        InputAction::MoveActionDown => {
+            if let (true, Some(index)) = (editable, **cursor) {
+                if index + 1 < action_plan.len() {
+                    commands.trigger(SwapActions(index, index + 1));
+                    **cursor = Some(index + 1);
+                }
+            }
+        }
+        InputAction::RemoveAtCursor => {
+            if let (true, Some(index)) = (editable, **cursor) {
+                commands.trigger(RemoveAction(index));
+            }
+        }
+        InputAction::CopyShareCode => {
+            if in_game {
+                commands.trigger(CopyShareCode);
+            }
+        }
+        InputAction::PasteShareCode => {
+            if editable {
+                commands.trigger(PasteShareCode);
+            }
+        }
+        InputAction::ToggleView => {
+            if in_game {
+                commands.trigger(ToggleViewMode);
+            }
+        }
+    }
+}
@@ -12,20 +12,21 @@ use bevy_firework::{
     curve::{FireworkCurve, FireworkGradient},
     emission_shape::EmissionShape,
 };
-use bevy_platform::collections::hash_map::HashMap;
 use bevy_tweening::{
     Animator, AssetAnimator, EaseMethod, Lens, RepeatCount, Tween, asset_animator_system,
     lens::TransformPositionLens,
 };
 use std::{f32::consts::PI, sync::LazyLock, time::Duration};
 
+pub use simon_core::level::*;
+
 use crate::{
-    actions::{Action, CWRotation},
     assets::TextureAssets,
     delayed_command::{DelayedCommand, DelayedCommandExt},
     game_state::GameState,
     maybe::MaybeBundleExt,
     player::{LevelCompleted, SpawnPlayer},
+    replay::ReplayMode,
     ui::{challenges::ChallengeState, constants::BUTTON_SUCCESS_COLOR, settings::GameMode},
 };
 
@@ -38,7 +39,8 @@ impl Plugin for LevelPlugin {
             .add_systems(Update, spawn_level.run_if(in_state(GameState::InGame)))
             .add_systems(
                 Update,
-                asset_animator_system::<StandardMaterial, MeshMaterial3d<StandardMaterial>>,
+                asset_animator_system::<StandardMaterial, MeshMaterial3d<StandardMaterial>>
+                    .run_if(resource_exists::<Assets<StandardMaterial>>),
             )
             .add_observer(level_completed)
             .add_observer(load_next_level)
@@ -47,155 +49,9 @@ impl Plugin for LevelPlugin {
     }
 }
 
-#[derive(Debug, Clone)]
-pub enum Scene {
-    Start,
-    Dialogue,
-    Level(Level),
-    Loop,
-    Finish,
-}
-
-impl From<Level> for Scene {
-    fn from(value: Level) -> Self {
-        Self::Level(value)
-    }
-}
-
-#[derive(Debug, Clone, Resource, Default)]
-pub struct Level {
-    pub tiles: HashMap<(i32, i32), Tile>,
-    pub name: &'static str,
-    pub actions: Vec<Action>,
-    pub action_limit: usize,
-    pub command_challenge: Option<usize>,
-    pub step_challenge: Option<usize>,
-    pub waste_challenge: Option<usize>,
-}
-
-impl Level {
-    pub fn get(&self, position: (i32, i32)) -> Option<&Tile> {
-        self.tiles.get(&position)
-    }
-
-    pub fn builder() -> LevelBuilder {
-        LevelBuilder::new()
-    }
-}
-
-#[derive(Debug, Clone, Copy, Component, PartialEq, Eq)]
-pub enum Tile {
-    Start(CWRotation),
-    Basic,
-    Ice,
-    Wall,
-    CWRot,
-    CCWRot,
-    Finish,
-}
-
-impl Tile {
-    pub const DEFAULT_START: Self = Tile::Start(CWRotation::Zero);
-
-    pub fn rotation(self) -> CWRotation {
-        match self {
-            Tile::Start(rot) => rot,
-            _ => {
-                tracing::error!(tile =?self, "Tile::rotation called on non-rotatable tile");
-                CWRotation::default()
-            }
-        }
-    }
-}
-
 #[derive(Debug, Component)]
 pub struct Start;
 
-pub struct LevelBuilder(Level);
-
-impl LevelBuilder {
-    pub fn new() -> Self {
-        Self(Level {
-            tiles: HashMap::new(),
-            name: "Unnamed",
-            actions: vec![
-                Action::Forward,
-                Action::Right,
-                Action::Backward,
-                Action::Left,
-            ],
-            action_limit: 1,
-            command_challenge: None,
-            step_challenge: None,
-            waste_challenge: None,
-        })
-    }
-
-    pub fn block(mut self, lower: (i32, i32), upper: (i32, i32), tile: Tile) -> Self {
-        for x in lower.0..=upper.0 {
-            for y in lower.1..=upper.1 {
-                self.0.tiles.insert((x, y), tile);
-            }
-        }
-
-        self
-    }
-
-    pub fn insert(mut self, tiles: impl IntoIterator<Item = ((i32, i32), Tile)>) -> Self {
-        for (position, tile) in tiles {
-            self.0.tiles.insert(position, tile);
-        }
-        self
-    }
-
-    pub fn remove(mut self, tiles: impl IntoIterator<Item = (i32, i32)>) -> Self {
-        for position in tiles {
-            self.0.tiles.remove(&position);
-        }
-        self
-    }
-
-    pub fn actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
-        self.0.actions = Vec::from_iter(actions);
-        self
-    }
-
-    pub fn action_limit(mut self, limit: usize) -> Self {
-        self.0.action_limit = limit;
-        self
-    }
-
-    pub fn command_challenge(mut self, challenge: usize) -> Self {
-        self.0.command_challenge = Some(challenge);
-        self
-    }
-
-    pub fn step_challenge(mut self, challenge: usize) -> Self {
-        self.0.step_challenge = Some(challenge);
-        self
-    }
-
-    pub fn waste_challenge(mut self, challenge: usize) -> Self {
-        self.0.waste_challenge = Some(challenge);
-        self
-    }
-
-    pub fn name(mut self, name: &'static str) -> Self {
-        self.0.name = name;
-        self
-    }
-
-    pub fn build(self) -> Level {
-        self.0
-    }
-}
-
-impl Default for LevelBuilder {
-    fn default() -> Self {
-        Self::new()
-    }
-}
-
 static DIALOGUE_LEVEL: LazyLock<Level> = LazyLock::new(|| {
     LevelBuilder::new()
         .action_limit(0)
@@ -204,721 +60,26 @@ static DIALOGUE_LEVEL: LazyLock<Level> = LazyLock::new(|| {
         .build()
 });
 
-pub static SCENES: LazyLock<Vec<Scene>> = LazyLock::new(|| {
-    vec![
-        LevelBuilder::new()
-            .name("Lost")
-            .action_limit(1)
-            .actions([Action::Forward])
-            .block((-2, 0), (2, 0), Tile::Basic)
-            .insert([((-2, 0), Tile::DEFAULT_START), ((2, 0), Tile::Finish)])
-            .insert(from_pictogram(&["🧑🟦🟦🟦🟩"]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Arbitrary")
-            .action_limit(2)
-            .actions([Action::Forward, Action::Right])
-            .insert(from_pictogram(&[
-                "🧑🟦🟦🟦",
-                "🟦🟦🟦🟦",
-                "🟦🟦🟦🟦",
-                "🟦🟦🟦🟩",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Pothole")
-            .action_limit(2)
-            .insert(from_pictogram(&[
-                "🟦🟦🟦🟩",
-                "🟦🟦🟦⬛",
-                "🟦🟦🟦🟦",
-                "🧑🟦🟦🟦",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Noise")
-            .action_limit(3)
-            .insert(transform(
-                ANTI_CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🟦🟦🟦🟦🟦",
-                    "🟦⬛⬛🟦🟩",
-                    "🟦⬛🟦🟦🟦",
-                    "👩🟦🟦🟦🟦",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Obstructions")
-            .action_limit(3)
-            .command_challenge(2)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬛⬜⬛⬛",
-                    "⬛🧑🟦🟦⬛",
-                    "⬜🟦⬜🟦⬜",
-                    "⬛🟦🟦🟩⬛",
-                    "⬛⬛⬜⬛⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Choices")
-            .action_limit(6)
-            .command_challenge(3)
-            .step_challenge(8)
-            .waste_challenge(22)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🧑⬜⬛⬛⬜⬛⬛",
-                    "🟦🟦🟦🟦🟦🟦🟩",
-                    "⬛⬛🟦⬜🟦⬛⬛",
-                    "🟦🟦🟦🟦🟦🟦🟩",
-                    "🟦⬜⬛⬛⬜⬛⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Precarious")
-            .action_limit(6)
-            .command_challenge(4)
-            .step_challenge(8)
-            .waste_challenge(13)
-            .waste_challenge(22)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🟦🟦⬜⬛⬛⬛⬛",
-                    "🟦🟦🟦🟦⬛⬛⬛",
-                    "⬛⬛⬜🟦🟦🟦🟩",
-                    "🟦🟦🟦🟦⬛⬛⬛",
-                    "🧑🟦⬜⬛⬛⬛⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Hook")
-            .action_limit(5)
-            .command_challenge(4)
-            .step_challenge(7)
-            .waste_challenge(14)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬛⬛⬛⬜⬛",
-                    "⬜⬜🟦🟦🟦⬜",
-                    "🧑🟦🟦⬜🟩🟦",
-                    "⬛⬛⬜⬛⬛⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Crucible")
-            .action_limit(8)
-            .command_challenge(7)
-            .step_challenge(14)
-            .waste_challenge(23)
-            .insert(from_pictogram(&[
-                "⬛⬛🟦🟦🟦",
-                "⬜🟦🟦⬜🟦",
-                "🧑🟦⬜🟩🟦",
-                "⬛⬜⬛⬛⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Rift")
-            .action_limit(7)
-            .command_challenge(6)
-            .step_challenge(14)
-            .waste_challenge(24)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🟦⬜🟦⬜🟦⬜🟦",
-                "🟦🟦🟦🟦🟦🟦🟦",
-                "⬜🟦🧑⬛⬜🟦⬜",
-                "🟦🟦🟦⬛🟦🟦🟦",
-                "⬜🟦⬜⬛🟩🟦⬜",
-                "🟦🟦🟦🟦🟦🟦🟦",
-                "⬜🟦⬜🟦⬜🟦⬜",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Overshoot")
-            .action_limit(4)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛⬛🟦🟦",
-                "🧑🏂🏂🟦",
-                "⬛⬛🏂⬛",
-                "⬛⬛🟩⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Glide")
-            .action_limit(4)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛⬜⬛⬛⬛",
-                "⬛🏂🏂⬜⬛",
-                "⬜🏂🏂🏂🧑",
-                "⬛⬛🏂⬛⬛",
-                "⬛⬛🟩⬛⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Loops")
-            .action_limit(5)
-            .command_challenge(4)
-            .step_challenge(9)
-            .waste_challenge(13)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛⬛⬛🟦🏂🟦",
-                "⬜🟦⬜🟦🟩🏂",
-                "🟦🟦🏂🏂🏂🟦",
-                "⬜🧑⬜⬛⬛⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Gauntlet")
-            .action_limit(5)
-            .command_challenge(5)
-            .step_challenge(7)
-            .waste_challenge(9)
-            .insert(transform(
-                ANTI_CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬜⬛⬛⬛⬛",
-                    "⬛🏂🏂🏂⬜⬛",
-                    "⬛🏂🏂🏂🏂⬛",
-                    "⬜🏂🧑🏂🏂⬜",
-                    "⬜🏂🏂🏂🏂⬛",
-                    "⬛⬜🏂🏂⬜⬛",
-                    "⬛🏂🏂🏂🏂⬛",
-                    "⬜🏂🏂🏂🏂⬛",
-                    "⬛🟩🏂⬜🏂⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Esky")
-            .action_limit(5)
-            .command_challenge(4)
-            .step_challenge(8)
-            .waste_challenge(15)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬜⬜⬜⬜⬜⬜⬜⬜",
-                "⬜🧑🏂🏂🏂🏂🏂⬜",
-                "⬜⬛🟦🟦🏂🏂🏂⬜",
-                "⬜🟦🏂🏂🏂🟦🏂⬜",
-                "⬜🏂🏂🟦🟩🏂🏂⬜",
-                "⬜🟦🏂🟦🏂🟦🏂⬜",
-                "⬜🏂🟦🏂🏂🏂🏂⬜",
-                "⬜⬜⬜⬜⬜⬜⬜⬜",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Divert")
-            .action_limit(1)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🧑🟦🔃",
-                "⬛⬛🟦",
-                "⬛⬛🟩",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Pivot")
-            .action_limit(4)
-            .waste_challenge(4)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🧑🔃🟩",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Twirl")
-            .action_limit(4)
-            .waste_challenge(10)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🧑🔃🔃🔃🔃🔃🟩",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Dizzy")
-            .action_limit(4)
-            .insert(transform(
-                CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬛⬛⬛🟩",
-                    "⬛⬛⬛⬛🔃",
-                    "⬛⬛⬛⬛🔃",
-                    "⬛⬛⬛⬛🔃",
-                    "🧑🔃🔃🔃🟦",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("ZigZag")
-            .action_limit(8)
-            .command_challenge(4)
-            // .step_challenge(10)
-            .waste_challenge(18)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🧑🔄🔄⬛⬛⬛⬛",
-                    "⬛⬛🔄⬛⬛⬛⬛",
-                    "⬛⬛🔄🔄🔄⬛⬛",
-                    "⬛⬛⬛⬛🔄⬛⬛",
-                    "⬛⬛⬛⬛🔄🔄🟩",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Binary")
-            .action_limit(6)
-            .command_challenge(2)
-            .waste_challenge(19)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🧑🔄🔃🔄🔃🔄🔃🟩",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Two-Step")
-            .action_limit(4)
-            .waste_challenge(17)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🧑🔄🔄🔃🔃🔄🔄🟩",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Chess")
-            .action_limit(6)
-            .command_challenge(4)
-            .waste_challenge(25)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🔄🔃🔄🔃🔄🔃🟩",
-                    "🔃🔄🔃🔄🔃🔄🔃",
-                    "🔄🔃🔄🔃🔄🔃🔄",
-                    "🧑🔄🔃🔄🔃🔄🔃",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Progress")
-            .actions([Action::Forward, Action::Left, Action::Right])
-            .action_limit(1)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬛⬜",
-                    "🟩🧑🔄",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Support")
-            .action_limit(3)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬜",
-                    "🧑🔄",
-                    "⬛🟦",
-                    "🟩🟦",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Snail")
-            .action_limit(3)
-            .insert(transform(
-                ANTI_CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬜",
-                    "🧑🔄",
-                    "🟦🟦",
-                    "⬛🔃",
-                    "⬛🟩",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Trapped")
-            .action_limit(5)
-            .command_challenge(3)
-            .step_challenge(8)
-            .waste_challenge(14)
-            .insert(transform(
-                CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬛⬜🟦⬛⬛",
-                    "🧑🔄🟦⬛🟦",
-                    "⬛⬜🟦🟦🟦",
-                    "⬛⬛⬛⬛🟩",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Restricted")
-            .actions([Action::Forward, Action::Backward, Action::Right])
-            .action_limit(4)
-            .command_challenge(2)
-            .waste_challenge(10)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🟩🟦⬛",
-                    "🟦🧑🔄",
-                    "⬛🔄⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Swirl")
-            .actions([Action::Forward, Action::Backward, Action::Right])
-            .action_limit(5)
-            .command_challenge(3)
-            .step_challenge(7)
-            .waste_challenge(16)
-            .insert(transform(
-                DOUBLE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🟩🟦⬛⬛⬛⬛",
-                    "⬛🟦⬛⬛⬛⬛",
-                    "⬛🟦🟦⬛⬜⬛",
-                    "⬛⬜🧒🔄🔄⬛",
-                    "⬛⬛🔄⬛🔄⬛",
-                    "⬛⬛🔄🔄🔄⬜",
-                    "⬛⬛⬜⬛⬛⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Squeeze")
-            .action_limit(6)
-            .command_challenge(5)
-            .step_challenge(11)
-            .waste_challenge(23)
-            .insert(transform(
-                CLOCKWISE,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "🟩🟦🔄",
-                    "⬜🟦⬜",
-                    "⬛🔃⬛",
-                    "🟦🔃🟦",
-                    "⬛⬜🔄",
-                    "⬛🟦🧑",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Popsicle")
-            .action_limit(7)
-            .command_challenge(5)
-            .step_challenge(10)
-            .waste_challenge(22)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬜🔄🟦🔃⬜",
-                    "⬛🟦⬛🔃⬜",
-                    "⬜🔄🟦🔃⬜",
-                    "⬛🟦⬛🟦⬜",
-                    "⬜🔄⬛🔃⬜",
-                    "⬛🧑⬛🟩⬛",
-                ]),
-            ))
-            .build()
-            .into(),
-        // LevelBuilder::new()
-        //     .name("Spinors")
-        //     .action_limit(5)
-        //     .command_challenge(2)
-        //     .step_challenge(10)
-        //     .waste_challenge(36)
-        //     .insert(from_pictogram(&[
-        //         #[rustfmt::ignore]
-        //         "🔃🔃🔄🟩",
-        //         "🔃🔃🔄🟦",
-        //         "🔄🔄🔃🔄",
-        //         "🔃🔄🔃🔄",
-        //         "🟦🔄🔃🔃",
-        //         "🧑🔃🔄🔃",
-        //     ]))
-        //     .build()
-        //     .into(),
-        LevelBuilder::new()
-            .name("Blizzard")
-            .action_limit(8)
-            .command_challenge(3)
-            .step_challenge(8)
-            .waste_challenge(70)
-            .insert(transform(
-                IDENTITY,
-                from_pictogram(&[
-                    #[rustfmt::ignore]
-                    "⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜",
-                    "⬜🔄🏂🏂🏂🏂🏂🏂🏂🔄⬜",
-                    "⬜🏂🔄🏂🏂🏂🏂🏂🟦🏂⬜",
-                    "⬜🏂🏂🔃🏂🟦🔃🏂🏂🏂⬜",
-                    "⬜🏂🏂🏂🧑⬜🟩🟦🏂🏂⬜",
-                    "⬜🏂🏂🔃🏂🟦🔃🏂🏂🏂⬜",
-                    "⬜🏂🟦🏂🏂🏂🏂🏂🏂🟦⬜",
-                    "⬜🔄🏂🏂🔄⬜🏂🏂🔄🔄⬜",
-                    "⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜",
-                ]),
-            ))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Mirror")
-            .action_limit(1)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🧑🟦🟦🟩",
-                "⬛⬛⬛⬛",
-                "🟩🟦🟦👩",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Duality")
-            .action_limit(3)
-            // .command_challenge(3)
-            // .step_challenge(6)
-            // .waste_challenge(31)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛🟦🏂🟩",
-                "⬛🟦🏂🟦",
-                "🧑🏂🏂⬜",
-                "⬜🏂🏂👩",
-                "🟦🏂🟦⬛",
-                "🟩🏂🟦⬛",
-            ]))
-            .build()
-            .into(),
-        // Scene::Start,
-        // Forward, Forward, Right, Backward, Right,
-        LevelBuilder::new()
-            .name("Unnamed")
-            .action_limit(5)
-            .command_challenge(5)
-            // .step_challenge(6)
-            // .waste_challenge(31)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "🧑🟩⬜⬛⬛",
-                "🟦🟦🟦🟩⬛",
-                "🟦⬜🟦🟦⬜",
-                "🟩⬛🟦🟦👩",
-            ]))
-            .build()
-            .into(),
-        // Scene::Loop,
-        LevelBuilder::new()
-            .name("Convergence")
-            .action_limit(6)
-            .command_challenge(4)
-            .step_challenge(11)
-            .waste_challenge(31)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬜⬛⬛⬛⬛⬛",
-                "🧑⬜⬜⬜⬛⬛",
-                "🟦🟦🟦🟦⬜⬛",
-                "🟦🟦🟦🟦🟦🟩",
-                "🟦🟦🟦🟦⬜⬛",
-                "🧑⬜⬜⬜⬛⬛",
-                "⬜⬛⬛⬛⬛⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Perpendicular")
-            .action_limit(6)
-            .command_challenge(4)
-            .step_challenge(7)
-            .waste_challenge(15)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬜⬜⬜⬜⬜⬜⬜⬜⬜",
-                "⬜🟩🏂🏂⬜🏂🏂🟩⬜",
-                "⬜🏂🏂🏂🏂🏂⬜🏂⬜",
-                "⬜🏂⬜🏂🏂🏂🏂🏂⬜",
-                "⬜🧑🏂🏂⬜🏂🏂🏂⬜",
-                "⬜🏂⬜🏂🏂🏂⬜🏂⬜",
-                "⬜🏂🏂⬜🏂🏂🏂🏂⬜",
-                "⬜⬜🏂🏂🧓🏂🏂⬜⬜",
-                "⬜⬜⬜⬜⬜⬜⬜⬜⬜",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Samsara")
-            .action_limit(6)
-            .command_challenge(3)
-            .step_challenge(11)
-            .waste_challenge(55)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛⬜⬛⬛⬜⬛⬜⬛⬛⬜⬛",
-                "⬜🟩🏂🏂🔃🏂🔃🏂🏂🔄⬜",
-                "⬛🏂🏂🏂🏂🏂🏂🏂🏂🏂⬛",
-                "⬛🏂🏂🏂🔄🏂🏂🏂🏂🏂⬛",
-                "⬜🔃🏂🏂🧓🏂🧑🔄🏂🔄⬜",
-                "⬛🏂🏂🏂🏂⬛🏂🏂🏂🏂⬛",
-                "⬜🔃🏂🔄👩🏂🧒🏂🏂🔄⬜",
-                "⬛🏂🏂🏂🏂🏂🔄🏂🏂🏂⬛",
-                "⬛🏂🏂🏂🏂🏂🏂🏂🏂🏂⬛",
-                "⬜🏂🏂🏂🔄🏂🔄🏂🏂🟩⬜",
-                "⬛⬜⬛⬛⬜⬛⬜⬛⬛⬜⬛",
-            ]))
-            .build()
-            .into(),
-        LevelBuilder::new()
-            .name("Transcendence")
-            .action_limit(6)
-            .command_challenge(6)
-            .step_challenge(18)
-            .waste_challenge(31)
-            .insert(from_pictogram(&[
-                #[rustfmt::ignore]
-                "⬛⬛⬛⬛⬛⬛⬛🧒⬛⬛⬛⬛⬛⬛⬛",
-                "⬛⬛⬛⬛⬛⬛🧒🟦🧒⬛⬛⬛⬛⬛⬛",
-                "⬛⬛⬛⬛⬛🧒🟦🟦🟦🧒⬛⬛⬛⬛⬛",
-                "⬛⬛⬛⬜🧒🟦🟦🟦🟦🟦🧒⬜⬛⬛⬛",
-                "⬛⬛⬛🧑⬜🟦🟦🟦🟦🟦⬜👩⬛⬛⬛",
-                "⬛⬛🧑🟦🟦⬜🟦🟦🟦⬜🟦🟦👩⬛⬛",
-                "⬛🧑🟦🟦🟦🟦⬜🟦⬜🟦🟦🟦🟦👩⬛",
-                "🧑🟦🟦🟦🟦🟦🟦🟩🟦🟦🟦🟦🟦🟦👩",
-                "⬛🧑🟦🟦🟦🟦⬜🟦⬜🟦🟦🟦🟦👩⬛",
-                "⬛⬛🧑🟦🟦⬜🟦🟦🟦⬜🟦🟦👩⬛⬛",
-                "⬛⬛⬛🧑⬜🟦🟦🟦🟦🟦⬜👩⬛⬛⬛",
-                "⬛⬛⬛⬜🧓🟦🟦🟦🟦🟦🧓⬜⬛⬛⬛",
-                "⬛⬛⬛⬛⬛🧓🟦🟦🟦🧓⬛⬛⬛⬛⬛",
-                "⬛⬛⬛⬛⬛⬛🧓🟦🧓⬛⬛⬛⬛⬛⬛",
-                "⬛⬛⬛⬛⬛⬛⬛🧓⬛⬛⬛⬛⬛⬛⬛",
-            ]))
-            .build()
-            .into(),
-    ]
-});
-
-const ANTI_CLOCKWISE: ((i32, i32), (i32, i32)) = ((0, 1), (1, 0));
-const CLOCKWISE: ((i32, i32), (i32, i32)) = ((0, -1), (1, 0));
-const DOUBLE: ((i32, i32), (i32, i32)) = ((-1, 0), (0, -1));
-const IDENTITY: ((i32, i32), (i32, i32)) = ((1, 0), (0, 1));
-
-fn transform(
-    rot: ((i32, i32), (i32, i32)),
-    iter: impl IntoIterator<Item = ((i32, i32), Tile)>,
-) -> impl IntoIterator<Item = ((i32, i32), Tile)> {
-    iter.into_iter()
-        .map(|((x, y), tile)| ((x * rot.0.0 + y * rot.0.1, x * rot.1.0 + y * rot.1.1), tile))
-        .collect::<Vec<_>>()
-}
-
-fn from_pictogram(lines: &[&str]) -> impl IntoIterator<Item = ((i32, i32), Tile)> {
-    let width = lines[0].chars().count() as i32;
-    let length = lines.len() as i32;
-
-    lines
-        .iter()
-        .flat_map(|line| line.chars())
-        .enumerate()
-        .filter_map(|(index, c)| {
-            let index = index as i32;
-            let coords = (index % width - width / 2, index / width - length / 2);
+#[derive(Debug, Default, Resource, Deref)]
+pub struct TileMesh(Handle<Mesh>);
 
-            match c {
-                '⬛' => None,
-                '🟩' => Some((coords, Tile::Finish)),
-                '⬜' => Some((coords, Tile::Wall)),
-                '🟦' => Some((coords, Tile::Basic)),
-                '🏂' => Some((coords, Tile::Ice)),
-                '🧑' => Some((coords, Tile::Start(CWRotation::Zero))),
-                '🧒' => Some((coords, Tile::Start(CWRotation::Ninety))),
-                '👩' => Some((coords, Tile::Start(CWRotation::OneEighty))),
-                '🧓' => Some((coords, Tile::Start(CWRotation::TwoSeventy))),
-                '🔃' => Some((coords, Tile::CWRot)),
-                '🔄' => Some((coords, Tile::CCWRot)),
-                other => {
-                    tracing::warn!(?other, "unrecognised pictogram");
-                    None
-                }
-            }
-        })
-        .collect::<Vec<_>>()
+const BASIC_COLOR: Color = Color::srgb_u8(0x3b, 0x5d, 0xc9);
+const WALL_COLOR: Color = Color::srgb_u8(0x56, 0x6c, 0x86);
+const ICE_COLOR: Color = Color::srgb_u8(0x73, 0xef, 0xf7);
+const ROTATION_COLOR: Color = Color::srgb_u8(0xff, 0x7c, 0x5D);
+
+/// The base colour of each tile's material, for drawing tiles without their meshes.
+pub fn tile_color(tile: Tile) -> Color {
+    match tile {
+        Tile::Basic | Tile::Start(_) => BASIC_COLOR,
+        Tile::Wall => WALL_COLOR,
+        Tile::Finish => BUTTON_SUCCESS_COLOR,
+        Tile::Ice => ICE_COLOR,
+        Tile::CWRot | Tile::CCWRot => ROTATION_COLOR,
+    }
 }
 
-#[derive(Debug, Resource, Deref)]
-pub struct TileMesh(Handle<Mesh>);
-
-#[derive(Debug, Resource)]
+#[derive(Debug, Default, Resource)]
 pub struct TileMaterials {
     pub basic: Handle<StandardMaterial>,
     pub wall: Handle<StandardMaterial>,
@@ -960,11 +121,11 @@ fn create_textures(
         meshes.add(Mesh::from(Cuboid::new(0.95, 0.95, 0.95)).with_generated_tangents()?);
     commands.insert_resource(TileMesh(tile_handle));
 
-    let basic = materials.add(Color::srgb_u8(0x3b, 0x5d, 0xc9));
+    let basic = materials.add(BASIC_COLOR);
     let finish = materials.add(BUTTON_SUCCESS_COLOR);
-    let wall = materials.add(Color::srgb_u8(0x56, 0x6c, 0x86));
+    let wall = materials.add(WALL_COLOR);
     let ice = materials.add(StandardMaterial {
-        base_color: Color::srgb_u8(0x73, 0xef, 0xf7),
+        base_color: ICE_COLOR,
         perceptual_roughness: 0.2,
         diffuse_transmission: 0.0,
         specular_transmission: 0.65,
@@ -978,13 +139,13 @@ fn create_textures(
 
     let cw_rot = materials.add(StandardMaterial {
         base_color_texture: Some(textures.cw_rot.clone()),
-        base_color: Color::srgb_u8(0xff, 0x7c, 0x5D),
+        base_color: ROTATION_COLOR,
         ..default()
     });
 
     let ccw_rot = materials.add(StandardMaterial {
         base_color_texture: Some(textures.ccw_rot.clone()),
-        base_color: Color::srgb_u8(0xff, 0x7c, 0x5D),
+        base_color: ROTATION_COLOR,
         ..default()
     });
 
@@ -1177,7 +338,16 @@ fn spawn_level(
 #[derive(Debug, Event)]
 pub struct GameFinished;
 
-fn level_completed(_trigger: Trigger<LevelCompleted>, mut commands: Commands) {
+fn level_completed(
+    _trigger: Trigger<LevelCompleted>,
+    mut commands: Commands,
+    replay: Res<ReplayMode>,
+) {
+    // Replays finish on the level they were watched on
+    if replay.is_active() {
+        return;
+    }
+
     commands.trigger(DespawnLevel);
 
     commands.spawn(DelayedCommand::new(2., move |commands| {
@@ -1232,14 +402,11 @@ fn load_next_level(
     game_mode: Res<GameMode>,
 ) {
     if *game_mode == GameMode::Challenge {
-        let challenges = challenges.get(level.name).unwrap();
+        let record = challenges.get(level.id).cloned().unwrap_or_default();
 
-        match (challenges.commands, challenges.steps, challenges.waste) {
-            (Some(false), _, _) | (_, Some(false), _) | (_, _, Some(false)) => {
-                *level = level.clone();
-                return;
-            }
-            _ => {}
+        if !record.all_completed(&level) {
+            *level = level.clone();
+            return;
         }
     }
 
@@ -5,13 +5,25 @@
 
 pub mod actions;
 pub mod assets;
+pub mod camera;
+pub mod clipboard;
 pub mod delayed_command;
 pub mod eyes;
 pub mod game_state;
+pub mod input_map;
 pub mod level;
 pub mod maybe;
 pub mod music;
 pub mod player;
+pub mod profile;
+pub mod progression;
+pub mod replay;
+pub mod save;
+pub mod save_transfer;
 pub mod simulation;
+#[cfg(test)]
+pub mod test_app;
 pub mod ui;
 pub mod video_glitch;
+
+pub use simon_core::{challenge, level_file, share_code, solver};
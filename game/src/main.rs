@@ -5,11 +5,20 @@ use bevy_firework::plugin::ParticleSystemPlugin;
 use bevy_tweening::TweeningPlugin;
 use game::assets::AssetsPlugin;
 use game::game_state::GameStatePlugin;
+use game::input_map::InputMapPlugin;
 use game::music::MusicPlugin;
+use game::profile::ProfilePlugin;
+use game::replay::ReplayPlugin;
 use game::video_glitch::VideoGlitchPlugin;
 use game::{
-    actions::ActionPlugin, delayed_command::DelayedCommandPlugin, eyes::EyesPlugin,
-    level::LevelPlugin, player::PlayerPlugin, simulation::SimulationPlugin, ui::UIPlugin,
+    actions::ActionPlugin,
+    camera::{CameraPlugin, LevelCamera},
+    delayed_command::DelayedCommandPlugin,
+    eyes::EyesPlugin,
+    level::LevelPlugin,
+    player::PlayerPlugin,
+    simulation::SimulationPlugin,
+    ui::UIPlugin,
 };
 
 fn main() {
@@ -45,6 +54,10 @@ fn main() {
     .add_plugins(MusicPlugin)
     .add_plugins(VideoGlitchPlugin)
     .add_plugins(AssetsPlugin)
+    .add_plugins(InputMapPlugin)
+    .add_plugins(ReplayPlugin)
+    .add_plugins(ProfilePlugin)
+    .add_plugins(CameraPlugin)
     // .insert_resource(ClearColor(Color::srgb_u8(0x33, 0x3c, 0x57)))
     .insert_resource(ClearColor(Color::srgb_u8(0xdd, 0xdd, 0xdd)))
     .insert_resource(AmbientLight {
@@ -83,13 +96,13 @@ fn setup(mut commands: Commands) {
         },
     ));
 
-    let mut transform = Transform::from_xyz(-3., 5.5, 9.0).looking_at(Vec3::ZERO, Vec3::Y);
-    transform.translation += Vec3::Y * 1.0;
+    let camera = LevelCamera::default();
 
     // camera
     commands.spawn((
         Camera3d::default(),
-        transform,
+        camera.transform(),
+        camera,
         Camera {
             hdr: false,
             ..default()
@@ -2,6 +2,7 @@ use std::{cell::RefCell, time::Duration};
 
 use bevy::prelude::*;
 use bevy_kira_audio::prelude::*;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     assets::{MusicAssets, SoundAssets},
@@ -59,7 +60,7 @@ impl Plugin for MusicPlugin {
     }
 }
 
-#[derive(Debug, Clone, Resource, Copy)]
+#[derive(Debug, Clone, Resource, Copy, Serialize, Deserialize)]
 pub enum MasterVolume {
     Muted,
     Unmuted,
@@ -17,6 +17,8 @@ use crate::{
     simulation::{SimulationEvent, SimulationPause, SimulationStop, run_simulation_step},
 };
 
+pub use simon_core::player::Player;
+
 pub struct PlayerPlugin;
 
 impl Plugin for PlayerPlugin {
@@ -81,29 +83,20 @@ fn uncullable_mesh(
     }
 }
 
-#[derive(Debug, Component, Default, Clone, Copy, PartialEq, Eq, Hash)]
-pub struct Player {
-    pub position: (i32, i32),
-    pub rotation: CWRotation,
-}
-
-impl From<Player> for Vec3 {
-    fn from(value: Player) -> Self {
-        Vec3::new(
-            value.position.0 as f32,
-            PLAYER_Y_OFFSET,
-            value.position.1 as f32,
-        )
-    }
+fn player_translation(player: Player) -> Vec3 {
+    Vec3::new(
+        player.position.0 as f32,
+        PLAYER_Y_OFFSET,
+        player.position.1 as f32,
+    )
 }
 
-impl<'a> From<&'a Player> for Vec3 {
-    fn from(value: &'a Player) -> Self {
-        Vec3::new(
-            value.position.0 as f32,
-            PLAYER_Y_OFFSET,
-            value.position.1 as f32,
-        )
+fn player_rotation(rotation: CWRotation) -> Quat {
+    match rotation {
+        CWRotation::Zero => Quat::from_rotation_y(std::f32::consts::FRAC_PI_2),
+        CWRotation::Ninety => Quat::IDENTITY,
+        CWRotation::OneEighty => Quat::from_rotation_y(-std::f32::consts::FRAC_PI_2),
+        CWRotation::TwoSeventy => Quat::from_rotation_y(-std::f32::consts::PI),
     }
 }
 
@@ -144,7 +137,7 @@ pub fn spawn_player(
                 SceneRoot(mesh.player.clone()),
                 Transform {
                     translation: position + Vec3::Y * 10.0,
-                    rotation: player.rotation.to_quat(),
+                    rotation: player_rotation(player.rotation),
                     scale: Vec3::ONE * 0.25,
                 },
                 Animator::new(Tween::new(
@@ -188,7 +181,7 @@ fn animate_player_movement(
         },
     ));
 
-    let desired_rotation = player.rotation.to_quat();
+    let desired_rotation = player_rotation(player.rotation);
 
     let tilt = match action {
         Action::Forward => Quat::from_rotation_z(-0.2) * desired_rotation,
@@ -281,9 +274,14 @@ struct PlayPlayerDeathSound;
 
 fn play_player_death_sound(
     _trigger: Trigger<PlayPlayerDeathSound>,
-    sounds: Res<SoundAssets>,
-    effect_channel: Res<AudioChannel<EffectChannel>>,
+    sounds: Option<Res<SoundAssets>>,
+    effect_channel: Option<Res<AudioChannel<EffectChannel>>>,
 ) {
+    // Headless apps have no audio
+    let (Some(sounds), Some(effect_channel)) = (sounds, effect_channel) else {
+        return;
+    };
+
     effect_channel
         .play(sounds.death_glitch.clone())
         .with_volume(0.2)
@@ -303,8 +301,8 @@ fn player_death(trigger: Trigger<Death>, mut commands: Commands, query: Query<&P
             EaseFunction::QuadraticIn,
             Duration::from_secs_f32(1.0),
             TransformPositionLens {
-                start: Vec3::from(player),
-                end: Vec3::from(player) + Vec3::Y * -20.,
+                start: player_translation(player),
+                end: player_translation(player) + Vec3::Y * -20.,
             },
         )));
     });
@@ -335,8 +333,8 @@ fn despawn_player(
                 EaseFunction::QuadraticIn,
                 Duration::from_secs_f32(1.0),
                 TransformPositionLens {
-                    start: Vec3::from(player),
-                    end: Vec3::from(player) + Vec3::Y * 10.,
+                    start: player_translation(player),
+                    end: player_translation(player) + Vec3::Y * 10.,
                 },
             )));
         }));
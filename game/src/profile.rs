@@ -0,0 +1,368 @@
+use bevy::prelude::*;
+use bevy_pkv::PkvStore;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    save::{self, SaveData, SaveFile},
+    save_transfer,
+};
+
+pub struct ProfilePlugin;
+
+impl Plugin for ProfilePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Profiles::default())
+            .insert_resource(PendingImport::default())
+            .insert_resource(TransferStatus::default())
+            .add_systems(Startup, load_profiles)
+            .add_systems(Update, (save_profiles, save_active_profile))
+            .add_systems(Update, poll_import)
+            .add_observer(switch_profile)
+            .add_observer(create_profile)
+            .add_observer(rename_profile)
+            .add_observer(delete_profile)
+            .add_observer(export_save)
+            .add_observer(import_save)
+            .add_observer(read_import)
+            .add_observer(apply_import)
+            .add_observer(cancel_import);
+    }
+}
+
+pub const MAX_PROFILE_NAME_LENGTH: usize = 16;
+
+const PROFILES_KEY: &str = "profiles";
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Profile {
+    pub id: u32,
+    pub name: String,
+}
+
+/// Everyone playing on this machine, each profile's [`SaveFile`] is stored under its own key.
+#[derive(Debug, Clone, Resource, Serialize, Deserialize)]
+pub struct Profiles {
+    profiles: Vec<Profile>,
+    active: u32,
+    next_id: u32,
+}
+
+impl Default for Profiles {
+    fn default() -> Self {
+        Profiles {
+            profiles: vec![Profile {
+                id: 0,
+                name: "Player 1".into(),
+            }],
+            active: 0,
+            next_id: 1,
+        }
+    }
+}
+
+impl Profiles {
+    pub fn iter(&self) -> impl Iterator<Item = &Profile> {
+        self.profiles.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.profiles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.profiles.is_empty()
+    }
+
+    pub fn active(&self) -> &Profile {
+        self.profiles
+            .iter()
+            .find(|profile| profile.id == self.active)
+            .unwrap_or(&self.profiles[0])
+    }
+
+    fn contains(&self, id: u32) -> bool {
+        self.profiles.iter().any(|profile| profile.id == id)
+    }
+
+    fn create(&mut self) -> u32 {
+        let id = self.next_id;
+        self.next_id += 1;
+
+        let name = (self.profiles.len() + 1..)
+            .map(|n| format!("Player {n}"))
+            .find(|name| self.profiles.iter().all(|profile| &profile.name != name))
+            .unwrap();
+
+        self.profiles.push(Profile { id, name });
+        id
+    }
+}
+
+fn save_key(id: u32) -> String {
+    format!("save_{id}")
+}
+
+/// Makes the profile with this id the active one, loading its save.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct SwitchProfile(pub u32);
+
+/// Adds a profile with a placeholder name and switches to it.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CreateProfile;
+
+#[derive(Debug, Clone, Event)]
+pub struct RenameProfile {
+    pub id: u32,
+    pub name: String,
+}
+
+/// Removes a profile and its save, the last profile can't be deleted.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct DeleteProfile(pub u32);
+
+/// Writes the active profile's save to a file, or downloads it on the web.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ExportSave;
+
+/// Reads a save exported by [`ExportSave`], waiting in [`PendingImport`] for confirmation.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ImportSave;
+
+#[derive(Debug, Clone, Event)]
+struct ReadImport(String);
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub enum ApplyImport {
+    /// Keep the best of the current and imported progress.
+    Merge,
+    /// Throw the current progress away.
+    Replace,
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CancelImport;
+
+/// An imported save waiting to be merged or to replace the active profile.
+#[derive(Debug, Clone, Default, Resource, Deref)]
+pub struct PendingImport(Option<SaveFile>);
+
+/// The outcome of the last export or import.
+#[derive(Debug, Clone, Default, Resource, Deref, DerefMut)]
+pub struct TransferStatus(pub String);
+
+fn load_profiles(pkv: Res<PkvStore>, mut profiles: ResMut<Profiles>, mut data: SaveData) {
+    let save = match pkv.get::<Profiles>(PROFILES_KEY) {
+        Ok(from_storage) if !from_storage.is_empty() => {
+            *profiles = from_storage;
+            save::read_save(&pkv, &save_key(profiles.active().id))
+        }
+        // First run with profiles, whatever was saved before becomes the first profile
+        _ => save::read_legacy_save(&pkv),
+    };
+
+    match save {
+        Some(Ok(save)) => data.apply(save),
+        Some(Err(err)) => tracing::error!(%err, "failed to load save"),
+        None => {}
+    }
+}
+
+fn load_profile(pkv: &PkvStore, data: &mut SaveData, id: u32) {
+    let save = match save::read_save(pkv, &save_key(id)) {
+        Some(Ok(save)) => save,
+        Some(Err(err)) => {
+            tracing::error!(%err, id, "failed to load profile, starting afresh");
+            SaveFile::default()
+        }
+        None => SaveFile::default(),
+    };
+
+    data.apply(save);
+}
+
+fn save_profiles(mut pkv: ResMut<PkvStore>, profiles: Res<Profiles>) {
+    if !profiles.is_changed() {
+        return;
+    }
+
+    if let Err(err) = pkv.set(PROFILES_KEY, &*profiles) {
+        tracing::error!(?err, "failed to save profiles");
+    }
+}
+
+fn save_active_profile(mut pkv: ResMut<PkvStore>, profiles: Res<Profiles>, data: SaveData) {
+    if !data.is_changed() {
+        return;
+    }
+
+    save::write_save(&mut pkv, &save_key(profiles.active().id), &data.snapshot());
+}
+
+fn switch_profile(
+    trigger: Trigger<SwitchProfile>,
+    mut pkv: ResMut<PkvStore>,
+    mut profiles: ResMut<Profiles>,
+    mut data: SaveData,
+) {
+    let id = trigger.event().0;
+
+    if id == profiles.active().id || !profiles.contains(id) {
+        return;
+    }
+
+    // Changes made this frame haven't been written yet
+    save::write_save(&mut pkv, &save_key(profiles.active().id), &data.snapshot());
+
+    profiles.active = id;
+    load_profile(&pkv, &mut data, id);
+}
+
+fn create_profile(
+    _trigger: Trigger<CreateProfile>,
+    mut commands: Commands,
+    mut profiles: ResMut<Profiles>,
+) {
+    let id = profiles.create();
+    commands.trigger(SwitchProfile(id));
+}
+
+fn rename_profile(trigger: Trigger<RenameProfile>, mut profiles: ResMut<Profiles>) {
+    let RenameProfile { id, name } = trigger.event();
+    let name: String = name.trim().chars().take(MAX_PROFILE_NAME_LENGTH).collect();
+
+    if name.is_empty() {
+        return;
+    }
+
+    if let Some(profile) = profiles
+        .profiles
+        .iter_mut()
+        .find(|profile| profile.id == *id)
+    {
+        profile.name = name;
+    }
+}
+
+fn delete_profile(
+    trigger: Trigger<DeleteProfile>,
+    mut pkv: ResMut<PkvStore>,
+    mut profiles: ResMut<Profiles>,
+    mut data: SaveData,
+) {
+    let id = trigger.event().0;
+
+    if profiles.len() == 1 || !profiles.contains(id) {
+        return;
+    }
+
+    profiles.profiles.retain(|profile| profile.id != id);
+
+    if let Err(err) = pkv.remove(&save_key(id)) {
+        tracing::error!(?err, id, "failed to remove profile save");
+    }
+
+    if profiles.active == id {
+        profiles.active = profiles.profiles[0].id;
+        let active = profiles.active;
+        load_profile(&pkv, &mut data, active);
+    }
+}
+
+fn export_save(_trigger: Trigger<ExportSave>, data: SaveData, mut status: ResMut<TransferStatus>) {
+    let export = data
+        .snapshot()
+        .to_json_pretty()
+        .map_err(|err| err.to_string())
+        .and_then(|json| save_transfer::export(&json));
+
+    **status = match export {
+        Ok(message) | Err(message) => message,
+    };
+}
+
+fn import_save(
+    _trigger: Trigger<ImportSave>,
+    mut commands: Commands,
+    mut status: ResMut<TransferStatus>,
+) {
+    match save_transfer::request_import() {
+        Some(Ok(json)) => commands.trigger(ReadImport(json)),
+        Some(Err(message)) => **status = message,
+        // The web file picker answers later, see poll_import
+        None => status.clear(),
+    }
+}
+
+fn poll_import(mut commands: Commands) {
+    if let Some(json) = save_transfer::poll_import() {
+        commands.trigger(ReadImport(json));
+    }
+}
+
+fn read_import(
+    trigger: Trigger<ReadImport>,
+    mut pending: ResMut<PendingImport>,
+    mut status: ResMut<TransferStatus>,
+) {
+    match SaveFile::from_json(&trigger.event().0) {
+        Ok(save) => {
+            pending.0 = Some(save);
+            status.clear();
+        }
+        Err(err) => **status = err.to_string(),
+    }
+}
+
+fn apply_import(
+    trigger: Trigger<ApplyImport>,
+    mut pending: ResMut<PendingImport>,
+    mut status: ResMut<TransferStatus>,
+    mut data: SaveData,
+) {
+    let Some(import) = pending.0.take() else {
+        return;
+    };
+
+    let save = match trigger.event() {
+        ApplyImport::Merge => {
+            let mut save = data.snapshot();
+            save.merge(&import);
+            save
+        }
+        ApplyImport::Replace => import,
+    };
+
+    **status = format!("Imported, {} levels completed", save.levels_completed());
+    data.apply(save);
+}
+
+fn cancel_import(
+    _trigger: Trigger<CancelImport>,
+    mut pending: ResMut<PendingImport>,
+    mut status: ResMut<TransferStatus>,
+) {
+    pending.0 = None;
+    status.clear();
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn created_names_are_unique() {
+        let mut profiles = Profiles::default();
+        let second = profiles.create();
+        profiles.profiles[0].name = "Player 3".into();
+        let third = profiles.create();
+
+        let names: Vec<_> = profiles
+            .iter()
+            .map(|profile| profile.name.as_str())
+            .collect();
+
+        assert_eq!(names, ["Player 3", "Player 2", "Player 4"]);
+        assert_ne!(second, third);
+        assert_eq!(profiles.active().id, 0);
+    }
+}
@@ -0,0 +1,211 @@
+//! Which levels can be picked from the level grid. Finishing a level always moves on to the next
+//! one, progression only decides how far ahead players may jump.
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    level::{Level, SCENES, Scene},
+    ui::challenges::ChallengeState,
+};
+
+/// Levels to a chapter, one row of the level grid.
+pub const CHAPTER_LENGTH: usize = 6;
+
+/// Challenges to complete for each chapter past the first.
+pub const CHAPTER_CHALLENGES: usize = 6;
+
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
+pub enum Progression {
+    /// Every level can be picked.
+    #[default]
+    Open,
+    /// A level unlocks once the one before it is completed.
+    Sequential,
+    /// Levels unlock a chapter at a time, every [`CHAPTER_CHALLENGES`] challenges completed.
+    Chapters,
+}
+
+impl Progression {
+    /// Whether each of [`SCENES`] can be picked, only levels ever can.
+    pub fn unlocked(self, challenges: &ChallengeState) -> Vec<bool> {
+        unlocked(&SCENES, self, challenges)
+    }
+}
+
+/// Play begins after [`Scene::Start`] and never gets past [`Scene::Loop`], so levels outside of
+/// that stay locked unless they've been completed. [`Scene::Dialogue`] isn't a level, it's skipped
+/// over and closes the chapter it's in.
+fn unlocked(scenes: &[Scene], progression: Progression, challenges: &ChallengeState) -> Vec<bool> {
+    let start = scenes
+        .iter()
+        .position(|scene| matches!(scene, Scene::Start))
+        .map_or(0, |index| index + 1);
+    let end = scenes[start..]
+        .iter()
+        .position(|scene| matches!(scene, Scene::Loop))
+        .map_or(scenes.len(), |index| start + index);
+
+    let record = |level: &Level| challenges.get(level.id).cloned().unwrap_or_default();
+    let challenges_completed: usize = scenes[start..end]
+        .iter()
+        .filter_map(|scene| match scene {
+            Scene::Level(level) => Some(level),
+            _ => None,
+        })
+        .map(|level| {
+            let record = record(level);
+            level
+                .challenges
+                .iter()
+                .filter(|challenge| record.is_completed(&***challenge))
+                .count()
+        })
+        .sum();
+
+    let mut previous_completed = true;
+    let mut chapter = 0;
+    let mut chapter_levels = 0;
+
+    scenes
+        .iter()
+        .enumerate()
+        .map(|(index, scene)| {
+            let level = match scene {
+                Scene::Level(level) => level,
+                Scene::Dialogue if chapter_levels > 0 => {
+                    chapter += 1;
+                    chapter_levels = 0;
+                    return false;
+                }
+                _ => return false,
+            };
+
+            let completed = record(level).level_completed;
+
+            if !(start..end).contains(&index) {
+                return completed || progression == Progression::Open;
+            }
+
+            if chapter_levels == CHAPTER_LENGTH {
+                chapter += 1;
+                chapter_levels = 0;
+            }
+            chapter_levels += 1;
+
+            let unlocked = match progression {
+                Progression::Open => true,
+                Progression::Sequential => previous_completed,
+                Progression::Chapters => challenges_completed >= chapter * CHAPTER_CHALLENGES,
+            };
+
+            previous_completed = completed;
+            unlocked || completed
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{challenge::ChallengeRecord, level::LevelBuilder};
+
+    fn level(id: &'static str) -> Scene {
+        LevelBuilder::new()
+            .id(id)
+            .name(id)
+            .command_challenge(1)
+            .build()
+            .into()
+    }
+
+    fn completed(ids: &[&str], challenges: bool) -> ChallengeState {
+        let mut state = ChallengeState::new();
+
+        for id in ids {
+            let mut record = ChallengeRecord {
+                level_completed: true,
+                ..default()
+            };
+
+            if challenges {
+                record.complete_challenge("commands".into(), false);
+            }
+
+            state.insert(id.to_string(), record);
+        }
+
+        state
+    }
+
+    #[test]
+    fn open_unlocks_every_level() {
+        let scenes = [
+            level("a"),
+            Scene::Dialogue,
+            level("b"),
+            Scene::Loop,
+            level("c"),
+        ];
+
+        assert_eq!(
+            unlocked(&scenes, Progression::Open, &completed(&[], false)),
+            [true, false, true, false, true]
+        );
+    }
+
+    #[test]
+    fn sequential_skips_dialogue_and_stops_at_the_loop() {
+        let scenes = [
+            level("a"),
+            Scene::Start,
+            level("b"),
+            Scene::Dialogue,
+            level("c"),
+            level("d"),
+            Scene::Loop,
+            level("e"),
+        ];
+
+        assert_eq!(
+            unlocked(&scenes, Progression::Sequential, &completed(&[], false)),
+            [false, false, true, false, false, false, false, false]
+        );
+        assert_eq!(
+            unlocked(
+                &scenes,
+                Progression::Sequential,
+                &completed(&["b", "c"], false)
+            ),
+            [false, false, true, false, true, true, false, false]
+        );
+        // Completing a level some other way, e.g. an imported save, always unlocks it
+        assert_eq!(
+            unlocked(&scenes, Progression::Sequential, &completed(&["e"], false)),
+            [false, false, true, false, false, false, false, true]
+        );
+    }
+
+    #[test]
+    fn chapters_unlock_with_challenges() {
+        let ids = [
+            "a", "b", "c", "d", "e", "f", "g", "h", "i", "j", "k", "l", "m", "n",
+        ];
+        let mut scenes: Vec<_> = ids.iter().map(|id| level(id)).collect();
+        // The dialogue cuts the second chapter short after "g" and "h", "i" to "n" make the third
+        scenes.insert(8, Scene::Dialogue);
+
+        let chapter = |state: &ChallengeState| {
+            let unlocked = unlocked(&scenes, Progression::Chapters, state);
+            [unlocked[0], unlocked[6], unlocked[14]]
+        };
+
+        assert_eq!(chapter(&completed(&[], false)), [true, false, false]);
+        // Completing levels doesn't count, only their challenges
+        assert_eq!(chapter(&completed(&ids[..6], false)), [true, false, false]);
+        assert_eq!(chapter(&completed(&ids[..6], true)), [true, true, false]);
+        assert_eq!(chapter(&completed(&ids[..11], true)), [true, true, false]);
+        assert_eq!(chapter(&completed(&ids[..12], true)), [true, true, true]);
+    }
+}
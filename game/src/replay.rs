@@ -0,0 +1,201 @@
+use std::collections::HashMap;
+
+use bevy::prelude::*;
+use serde::{Deserialize, Serialize};
+
+use crate::{
+    actions::{Action, ActionPlan},
+    delayed_command::DelayedCommandExt,
+    game_state::{GameState, unix_timestamp},
+    level::{self, DespawnLevel, Level, LevelCounter, SCENES},
+    music::PlayChangeLevelMusic,
+    player::{DespawnPlayer, LevelCompleted},
+    simulation::{SimulationStart, SimulationState, SimulationStop},
+    ui::{challenges::StepCount, settings::DestroySettingsUI},
+};
+
+pub struct ReplayPlugin;
+
+impl Plugin for ReplayPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Replays::default())
+            .insert_resource(ReplayMode::default())
+            .add_observer(record_replay)
+            .add_observer(watch_replay)
+            .add_observer(load_replay_level)
+            .add_observer(start_replay)
+            .add_observer(end_replay);
+    }
+}
+
+/// The most recent winning plan for a level.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Replay {
+    pub plan: Vec<Action>,
+    pub steps: usize,
+    /// Seconds since the Unix epoch.
+    pub timestamp: u64,
+}
+
+/// Replays keyed by [`Level::id`], like [`crate::ui::challenges::ChallengeState`].
+#[derive(Debug, Clone, Default, Resource, Deref, DerefMut, Serialize, Deserialize)]
+pub struct Replays(HashMap<String, Replay>);
+
+/// Set while a replay is playing, the level can't be edited or progressed until it finishes.
+#[derive(Debug, Clone, Default, Resource)]
+pub struct ReplayMode {
+    /// The player's own plan, put back once the replay is over.
+    restore: Option<ActionPlan>,
+}
+
+impl ReplayMode {
+    pub fn is_active(&self) -> bool {
+        self.restore.is_some()
+    }
+}
+
+fn record_replay(
+    _trigger: Trigger<LevelCompleted>,
+    mode: Res<ReplayMode>,
+    level: Res<Level>,
+    action_plan: Res<ActionPlan>,
+    step_count: Res<StepCount>,
+    mut replays: ResMut<Replays>,
+) {
+    // Dialogue scenes complete on a timer without a plan
+    if mode.is_active() || action_plan.is_empty() {
+        return;
+    }
+
+    replays.insert(
+        level.id.to_string(),
+        Replay {
+            plan: action_plan.0.clone(),
+            steps: **step_count,
+            timestamp: unix_timestamp(),
+        },
+    );
+}
+
+/// Loads the level at the given scene index and plays its stored replay.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct WatchReplay(pub usize);
+
+/// Loads `level_id` for a replay, unless another level was picked since it was asked for while
+/// the level counter was at `from`.
+#[derive(Debug, Clone, Event)]
+struct LoadReplayLevel {
+    from: usize,
+    level_id: usize,
+    plan: ActionPlan,
+}
+
+/// Plays `plan`, as long as `level_id` is still the level being played.
+#[derive(Debug, Clone, Event)]
+struct StartReplay {
+    level_id: usize,
+    plan: ActionPlan,
+}
+
+fn watch_replay(
+    trigger: Trigger<WatchReplay>,
+    mut commands: Commands,
+    replays: Res<Replays>,
+    level_counter: Res<LevelCounter>,
+    simulation_state: Res<State<SimulationState>>,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let level_id = trigger.event().0;
+
+    let Some(level::Scene::Level(level)) = SCENES.get(level_id) else {
+        tracing::warn!(level_id, "attempted to watch replay of non-level scene");
+        return;
+    };
+
+    let Some(replay) = replays.get(level.id) else {
+        tracing::warn!(level = level.id, "no replay recorded");
+        return;
+    };
+
+    let plan = ActionPlan(replay.plan.clone());
+
+    if *simulation_state != SimulationState::Stopped {
+        commands.trigger(SimulationStop);
+    }
+
+    commands.trigger(DestroySettingsUI);
+    commands.trigger(DespawnLevel);
+    commands.trigger(DespawnPlayer);
+    commands.trigger(PlayChangeLevelMusic);
+    game_state.set(GameState::InGame);
+    let from = **level_counter;
+    commands.delayed(2., move |commands| {
+        commands.trigger(LoadReplayLevel {
+            from,
+            level_id,
+            plan,
+        })
+    });
+}
+
+fn load_replay_level(
+    trigger: Trigger<LoadReplayLevel>,
+    mut commands: Commands,
+    level_counter: Res<LevelCounter>,
+) {
+    let &LoadReplayLevel {
+        from,
+        level_id,
+        ref plan,
+    } = trigger.event();
+
+    if **level_counter != from {
+        tracing::debug!(level_id, "level changed before the replay loaded");
+        return;
+    }
+
+    commands.trigger(level::LoadLevel(level_id));
+
+    let plan = plan.clone();
+    // Give the tiles and player time to settle before the first move
+    commands.delayed(1.5, move |commands| {
+        commands.trigger(StartReplay { level_id, plan })
+    });
+}
+
+fn start_replay(
+    trigger: Trigger<StartReplay>,
+    mut commands: Commands,
+    level_counter: Res<LevelCounter>,
+    mut mode: ResMut<ReplayMode>,
+    mut action_plan: ResMut<ActionPlan>,
+) {
+    let StartReplay { level_id, plan } = trigger.event();
+
+    if **level_counter != *level_id {
+        tracing::debug!(level_id, "level changed before the replay started");
+        return;
+    }
+
+    // Replays bypass the undo history, the player's plan as the level loaded is restored when it
+    // finishes
+    let plan = std::mem::replace(&mut *action_plan, plan.clone());
+    mode.restore.get_or_insert(plan);
+
+    commands.trigger(SimulationStart);
+}
+
+/// Runs once the replay completes the level, dies, or is reset.
+fn end_replay(
+    _trigger: Trigger<SimulationStop>,
+    mut mode: ResMut<ReplayMode>,
+    mut action_plan: ResMut<ActionPlan>,
+) {
+    if !mode.is_active() {
+        return;
+    }
+
+    if let Some(plan) = mode.restore.take() {
+        *action_plan = plan;
+    }
+}
@@ -0,0 +1,500 @@
+use std::{collections::HashMap, fmt};
+
+use bevy::{ecs::system::SystemParam, prelude::*};
+use bevy_pkv::PkvStore;
+use serde::{Deserialize, Serialize};
+use serde_json::{Value, json};
+
+use crate::{
+    input_map::InputMap,
+    level::{self, SCENES},
+    music::MasterVolume,
+    progression::Progression,
+    replay::Replays,
+    ui::{challenges::ChallengeState, settings::GameMode},
+};
+
+/// Bump this and add a step to [`MIGRATIONS`] whenever the shape of [`SaveFile`] changes.
+///
+/// 1. A bare [`ChallengeState`] keyed by level name, before `level_completed` was tracked.
+/// 2. The same, with `level_completed`.
+/// 3. The [`SaveFile`] envelope, keyed by [`level::Level::id`] and including replays.
+/// 4. Adds [`Settings`], which were global before profiles.
+/// 5. Challenge flags move into a map keyed by [`crate::challenge::Challenge::id`].
+/// 6. Adds [`Progression`] to the [`Settings`].
+pub const SCHEMA_VERSION: u32 = 6;
+
+/// Each step upgrades a save from version `index + 1` to the next.
+const MIGRATIONS: [fn(Value) -> Value; 5] =
+    [migrate_v1, migrate_v2, migrate_v3, migrate_v4, migrate_v5];
+
+/// The store saves are kept in, see [`crate::game_state::GameStatePlugin`].
+pub const STORE_ORGANIZATION: &str = "DylanRJohnston";
+pub const STORE_NAME: &str = "SimonSaysV2";
+
+/// Stores earlier releases saved to, newest first.
+const LEGACY_STORE_NAMES: &[&str] = &["SimonSays"];
+
+// Where saves lived before profiles
+const LEGACY_SAVE_KEY: &str = "save";
+const LEGACY_CHALLENGE_KEY: &str = "challenge_state";
+const LEGACY_REPLAYS_KEY: &str = "replays";
+const LEGACY_INPUT_MAP_KEY: &str = "input_map";
+
+/// Everything persisted about a profile, stored as JSON under a single key.
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct SaveFile {
+    pub schema_version: u32,
+    pub challenges: ChallengeState,
+    pub replays: Replays,
+    pub settings: Settings,
+}
+
+impl Default for SaveFile {
+    fn default() -> Self {
+        SaveFile {
+            schema_version: SCHEMA_VERSION,
+            challenges: ChallengeState::new(),
+            replays: Replays::default(),
+            settings: Settings::default(),
+        }
+    }
+}
+
+impl SaveFile {
+    /// Parses a save of any known version, migrating it to the current one.
+    pub fn from_json(json: &str) -> Result<Self, SaveError> {
+        migrate(serde_json::from_str(json).map_err(SaveError::Json)?)
+    }
+
+    pub fn to_json(&self) -> Result<String, SaveError> {
+        serde_json::to_string(self).map_err(SaveError::Json)
+    }
+
+    /// Indented, for exports people might open.
+    pub fn to_json_pretty(&self) -> Result<String, SaveError> {
+        serde_json::to_string_pretty(self).map_err(SaveError::Json)
+    }
+
+    /// Folds in progress from another save, e.g. one exported from the web build. Settings are
+    /// left alone since they're specific to the machine.
+    pub fn merge(&mut self, other: &SaveFile) {
+        for (id, record) in other.challenges.iter() {
+            self.challenges.entry(id.clone()).or_default().merge(record);
+        }
+
+        for (id, replay) in other.replays.iter() {
+            match self.replays.get(id) {
+                Some(existing) if existing.timestamp >= replay.timestamp => {}
+                _ => {
+                    self.replays.insert(id.clone(), replay.clone());
+                }
+            }
+        }
+    }
+
+    pub fn levels_completed(&self) -> usize {
+        self.challenges
+            .values()
+            .filter(|record| record.level_completed)
+            .count()
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct Settings {
+    pub game_mode: GameMode,
+    pub progression: Progression,
+    pub master_volume: MasterVolume,
+    pub input_map: InputMap,
+}
+
+impl Default for Settings {
+    fn default() -> Self {
+        Settings {
+            game_mode: GameMode::Story,
+            progression: Progression::Open,
+            master_volume: MasterVolume::Unmuted,
+            input_map: InputMap::default(),
+        }
+    }
+}
+
+/// The resources making up the active profile's save.
+#[derive(SystemParam)]
+pub struct SaveData<'w> {
+    challenges: ResMut<'w, ChallengeState>,
+    replays: ResMut<'w, Replays>,
+    game_mode: ResMut<'w, GameMode>,
+    progression: ResMut<'w, Progression>,
+    master_volume: ResMut<'w, MasterVolume>,
+    input_map: ResMut<'w, InputMap>,
+}
+
+impl SaveData<'_> {
+    pub fn is_changed(&self) -> bool {
+        self.challenges.is_changed()
+            || self.replays.is_changed()
+            || self.game_mode.is_changed()
+            || self.progression.is_changed()
+            || self.master_volume.is_changed()
+            || self.input_map.is_changed()
+    }
+
+    pub fn snapshot(&self) -> SaveFile {
+        SaveFile {
+            schema_version: SCHEMA_VERSION,
+            challenges: self.challenges.clone(),
+            replays: self.replays.clone(),
+            settings: Settings {
+                game_mode: *self.game_mode,
+                progression: *self.progression,
+                master_volume: *self.master_volume,
+                input_map: self.input_map.clone(),
+            },
+        }
+    }
+
+    pub fn apply(&mut self, mut save: SaveFile) {
+        // Levels added since the save was written keep their defaults
+        *self.challenges = ChallengeState::new();
+        self.challenges.extend(save.challenges.drain());
+        *self.replays = save.replays;
+        *self.game_mode = save.settings.game_mode;
+        *self.progression = save.settings.progression;
+        *self.master_volume = save.settings.master_volume;
+        save.settings.input_map.add_missing_defaults();
+        *self.input_map = save.settings.input_map;
+    }
+}
+
+#[derive(Debug)]
+pub enum SaveError {
+    Json(serde_json::Error),
+    UnsupportedVersion(u64),
+}
+
+impl fmt::Display for SaveError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            SaveError::Json(err) => write!(f, "The save couldn't be read: {err}"),
+            SaveError::UnsupportedVersion(version) => {
+                write!(
+                    f,
+                    "Save version {version} isn't supported by this version of the game"
+                )
+            }
+        }
+    }
+}
+
+/// Runs every migration needed to bring `save` up to [`SCHEMA_VERSION`].
+pub fn migrate(mut save: Value) -> Result<SaveFile, SaveError> {
+    let version = schema_version(&save);
+
+    if version == 0 || version > u64::from(SCHEMA_VERSION) {
+        return Err(SaveError::UnsupportedVersion(version));
+    }
+
+    for migration in &MIGRATIONS[version as usize - 1..] {
+        save = migration(save);
+    }
+
+    serde_json::from_value(save).map_err(SaveError::Json)
+}
+
+/// Versions 1 and 2 predate the envelope, they're told apart by `level_completed`.
+fn schema_version(save: &Value) -> u64 {
+    if let Some(version) = save.get("schema_version") {
+        return version.as_u64().unwrap_or_default();
+    }
+
+    let tracks_completion = save.as_object().is_some_and(|records| {
+        records
+            .values()
+            .all(|record| record.get("level_completed").is_some())
+    });
+
+    match tracks_completion {
+        true => 2,
+        false => 1,
+    }
+}
+
+/// Any level with a challenge met must have been completed.
+fn migrate_v1(mut save: Value) -> Value {
+    let Some(records) = save.as_object_mut() else {
+        return save;
+    };
+
+    for record in records.values_mut().filter_map(Value::as_object_mut) {
+        let completed = ["commands", "steps", "waste"]
+            .iter()
+            .any(|challenge| record.get(*challenge) == Some(&Value::Bool(true)));
+
+        record
+            .entry("level_completed")
+            .or_insert(Value::Bool(completed));
+    }
+
+    save
+}
+
+/// Wraps the challenges in the envelope and keys them by level id.
+fn migrate_v2(save: Value) -> Value {
+    let challenges = match save {
+        Value::Object(records) => records
+            .into_iter()
+            .map(|(name, record)| (level_id(&name), record))
+            .collect(),
+        other => other,
+    };
+
+    json!({
+        "schema_version": 3,
+        "challenges": challenges,
+        "replays": {},
+    })
+}
+
+fn migrate_v3(mut save: Value) -> Value {
+    save["schema_version"] = json!(4);
+    save["settings"] = json!(Settings::default());
+    save
+}
+
+/// The three built in challenges were fields, unset where the level didn't have them.
+fn migrate_v4(mut save: Value) -> Value {
+    save["schema_version"] = json!(5);
+
+    let Some(records) = save["challenges"].as_object_mut() else {
+        return save;
+    };
+
+    for record in records.values_mut().filter_map(Value::as_object_mut) {
+        let challenges: serde_json::Map<_, _> = ["commands", "steps", "waste"]
+            .into_iter()
+            .filter_map(|id| {
+                let completed = record.remove(id)?.as_bool()?;
+                Some((id.to_string(), Value::Bool(completed)))
+            })
+            .collect();
+
+        record.insert("challenges".into(), Value::Object(challenges));
+    }
+
+    save
+}
+
+fn migrate_v5(mut save: Value) -> Value {
+    save["schema_version"] = json!(6);
+    save["settings"]["progression"] = json!(Progression::default());
+    save
+}
+
+/// The id of the level called `name`, names without a level are kept so nothing is lost.
+fn level_id(name: &str) -> String {
+    SCENES
+        .iter()
+        .find_map(|scene| match scene {
+            level::Scene::Level(level) if level.name == name => Some(level.id),
+            _ => None,
+        })
+        .unwrap_or(name)
+        .to_string()
+}
+
+/// [`crate::ui::challenges::ChallengeRecord`] as it was stored in a version 1 or 2 save.
+#[derive(Debug, Serialize, Deserialize)]
+struct LegacyRecord {
+    commands: Option<bool>,
+    steps: Option<bool>,
+    waste: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    level_completed: Option<bool>,
+}
+
+/// Reads a save written under `key`, `None` if there isn't one.
+pub fn read_save(pkv: &PkvStore, key: &str) -> Option<Result<SaveFile, SaveError>> {
+    let json = pkv.get::<String>(key).ok()?;
+
+    Some(SaveFile::from_json(&json))
+}
+
+pub fn write_save(pkv: &mut PkvStore, key: &str, save: &SaveFile) {
+    let json = match save.to_json() {
+        Ok(json) => json,
+        Err(err) => {
+            tracing::error!(%err, "failed to serialize save");
+            return;
+        }
+    };
+
+    if let Err(err) = pkv.set_string(key, &json) {
+        tracing::error!(?err, key, "failed to save");
+    }
+}
+
+/// Reads the save from before profiles existed, in whichever form and store it was left.
+pub fn read_legacy_save(pkv: &PkvStore) -> Option<Result<SaveFile, SaveError>> {
+    read_legacy_store(pkv).or_else(|| {
+        LEGACY_STORE_NAMES
+            .iter()
+            .find_map(|name| read_legacy_store(&PkvStore::new(STORE_ORGANIZATION, name)))
+    })
+}
+
+fn read_legacy_store(pkv: &PkvStore) -> Option<Result<SaveFile, SaveError>> {
+    let save = match read_save(pkv, LEGACY_SAVE_KEY) {
+        Some(save) => save,
+        None => read_name_keyed_save(pkv)?,
+    };
+
+    // Bindings were stored on their own until settings joined the save
+    Some(save.map(|mut save| {
+        if let Ok(input_map) = pkv.get::<InputMap>(LEGACY_INPUT_MAP_KEY) {
+            save.settings.input_map = input_map;
+        }
+
+        save
+    }))
+}
+
+fn read_name_keyed_save(pkv: &PkvStore) -> Option<Result<SaveFile, SaveError>> {
+    let records = pkv
+        .get::<HashMap<String, LegacyRecord>>(LEGACY_CHALLENGE_KEY)
+        .ok()?;
+
+    let save = serde_json::to_value(records)
+        .map_err(SaveError::Json)
+        .and_then(migrate)
+        .map(|mut save| {
+            if let Ok(replays) = pkv.get::<Replays>(LEGACY_REPLAYS_KEY) {
+                save.replays.extend(
+                    replays
+                        .iter()
+                        .map(|(name, replay)| (level_id(name), replay.clone())),
+                );
+            }
+
+            save
+        });
+
+    Some(save)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::ui::challenges::ChallengeRecord;
+
+    fn record(save: &SaveFile, id: &str) -> ChallengeRecord {
+        save.challenges.get(id).unwrap().clone()
+    }
+
+    #[test]
+    fn loads_v1() {
+        let save = SaveFile::from_json(
+            r#"{
+                "Lost": { "commands": null, "steps": null, "waste": null },
+                "Crucible": { "commands": true, "steps": false, "waste": null },
+                "Two-Step": { "commands": false, "steps": false, "waste": null }
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(save.schema_version, SCHEMA_VERSION);
+        assert!(!record(&save, "lost").level_completed);
+        assert!(record(&save, "crucible").level_completed);
+        assert_eq!(record(&save, "crucible").challenges["commands"], true);
+        assert!(!record(&save, "crucible").challenges.contains_key("waste"));
+        assert!(!record(&save, "two-step").level_completed);
+        assert!(save.replays.is_empty());
+        assert_eq!(save.settings.game_mode, GameMode::Story);
+        assert_eq!(save.settings.progression, Progression::Open);
+    }
+
+    #[test]
+    fn loads_v2() {
+        let save = SaveFile::from_json(
+            r#"{
+                "Lost": { "commands": null, "steps": null, "waste": null, "level_completed": true },
+                "Precarious": { "commands": false, "steps": true, "waste": false, "level_completed": true },
+                "Removed Level": { "commands": null, "steps": null, "waste": null, "level_completed": true }
+            }"#,
+        )
+        .unwrap();
+
+        assert!(record(&save, "lost").level_completed);
+        assert_eq!(record(&save, "precarious").challenges["steps"], true);
+        assert_eq!(record(&save, "precarious").attempts, 0);
+        assert!(record(&save, "Removed Level").level_completed);
+        assert!(!save.challenges.contains_key("Lost"));
+    }
+
+    #[test]
+    fn round_trip() {
+        let mut challenges = ChallengeState::new();
+        challenges
+            .get_mut("rift")
+            .unwrap()
+            .record_completion(3, 12, 1_700_000_000);
+
+        let json = SaveFile {
+            challenges,
+            ..default()
+        }
+        .to_json()
+        .unwrap();
+        let save = SaveFile::from_json(&json).unwrap();
+
+        assert_eq!(record(&save, "rift").fewest_steps, Some(12));
+        assert_eq!(record(&save, "rift").first_completed, Some(1_700_000_000));
+    }
+
+    #[test]
+    fn merge_keeps_the_best_of_both() {
+        let mut home = SaveFile::default();
+        let mut work = SaveFile::default();
+
+        home.challenges
+            .get_mut("rift")
+            .unwrap()
+            .record_completion(4, 10, 200);
+        work.challenges
+            .get_mut("rift")
+            .unwrap()
+            .record_completion(5, 8, 100);
+        work.challenges.get_mut("lost").unwrap().attempts = 2;
+        work.settings.game_mode = GameMode::Challenge;
+
+        let home_rift = home.challenges.get_mut("rift").unwrap();
+        home_rift.complete_challenge("steps".into(), true);
+        home_rift.complete_challenge("commands".into(), true);
+        work.challenges
+            .get_mut("rift")
+            .unwrap()
+            .complete_challenge("steps".into(), false);
+
+        home.merge(&work);
+
+        let rift = record(&home, "rift");
+        assert_eq!(rift.fewest_commands, Some(4));
+        assert_eq!(rift.fewest_steps, Some(8));
+        assert_eq!(rift.most_steps, Some(10));
+        assert_eq!(rift.first_completed, Some(100));
+        assert_eq!(rift.assisted.iter().collect::<Vec<_>>(), vec!["commands"]);
+        assert_eq!(record(&home, "lost").attempts, 2);
+        assert_eq!(home.levels_completed(), 1);
+        assert_eq!(home.settings.game_mode, GameMode::Story);
+    }
+
+    #[test]
+    fn rejects_newer_versions() {
+        let save =
+            SaveFile::from_json(r#"{ "schema_version": 99, "challenges": {}, "replays": {} }"#);
+
+        assert!(matches!(save, Err(SaveError::UnsupportedVersion(99))));
+    }
+}
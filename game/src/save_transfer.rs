@@ -0,0 +1,68 @@
+//! Moving a save in and out of the game as a JSON file.
+//!
+//! Native builds use a file next to the executable. The web build downloads the export and reads
+//! imports through a file picker, which answers asynchronously so imports are polled for.
+
+pub const EXPORT_FILE_NAME: &str = "simon-says-save.json";
+
+#[cfg(not(target_arch = "wasm32"))]
+fn export_path() -> Result<std::path::PathBuf, String> {
+    std::env::current_exe()
+        .map(|exe| exe.with_file_name(EXPORT_FILE_NAME))
+        .map_err(|err| format!("Couldn't find the game's folder: {err}"))
+}
+
+/// Writes the export, returning a message saying where it went.
+#[cfg(not(target_arch = "wasm32"))]
+pub fn export(json: &str) -> Result<String, String> {
+    let path = export_path()?;
+
+    std::fs::write(&path, json)
+        .map(|_| format!("Exported to {}", path.display()))
+        .map_err(|err| format!("Couldn't write {}: {err}", path.display()))
+}
+
+/// Reads the export file, or starts the file picker on the web where the result comes later
+/// through [`poll_import`].
+#[cfg(not(target_arch = "wasm32"))]
+pub fn request_import() -> Option<Result<String, String>> {
+    let path = match export_path() {
+        Ok(path) => path,
+        Err(err) => return Some(Err(err)),
+    };
+
+    Some(
+        std::fs::read_to_string(&path)
+            .map_err(|err| format!("Couldn't read {}: {err}", path.display())),
+    )
+}
+
+#[cfg(not(target_arch = "wasm32"))]
+pub fn poll_import() -> Option<String> {
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+#[wasm_bindgen_macro::wasm_bindgen]
+extern "C" {
+    fn download_save_js(file_name: &str, contents: &str);
+    fn pick_save_file_js();
+    fn take_save_import_js() -> Option<String>;
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn export(json: &str) -> Result<String, String> {
+    download_save_js(EXPORT_FILE_NAME, json);
+    Ok(format!("Downloaded {EXPORT_FILE_NAME}"))
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn request_import() -> Option<Result<String, String>> {
+    pick_save_file_js();
+    None
+}
+
+#[cfg(target_arch = "wasm32")]
+pub fn poll_import() -> Option<String> {
+    take_save_import_js()
+}
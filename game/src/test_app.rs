@@ -0,0 +1,291 @@
+//! A headless [`App`] with the game logic plugins, for tests that drive the observers end to end.
+
+use std::time::Duration;
+
+use bevy::{prelude::*, state::app::StatesPlugin, time::TimeUpdateStrategy};
+
+use crate::{
+    actions::{Action, ActionPlan, ActionPlugin, LoadActionPlan},
+    assets::ModelAssets,
+    delayed_command::DelayedCommandPlugin,
+    game_state::GameState,
+    level::{Level, LevelCounter, LevelPlugin, LoadLevel, SCENES, Scene, TileMaterials, TileMesh},
+    player::{Death, LevelCompleted, PlayerPlugin},
+    replay::ReplayMode,
+    simulation::{SimulationPlugin, SimulationStart, SimulationState},
+    ui::{
+        challenges::{ChallengePlugin, ChallengeRecord, ChallengeState},
+        hint::HintPlugin,
+        settings::GameMode,
+    },
+};
+
+/// How far virtual time moves each update.
+pub const TICK: Duration = Duration::from_millis(50);
+
+pub struct TestApp {
+    app: App,
+}
+
+pub struct TestAppBuilder {
+    level: Option<&'static str>,
+    game_mode: GameMode,
+}
+
+/// Everything [`LevelCompleted`] and [`Death`] were triggered for since the app was built.
+#[derive(Debug, Default, Resource)]
+pub struct TriggerCounts {
+    pub levels_completed: usize,
+    pub deaths: usize,
+}
+
+impl TestAppBuilder {
+    /// Starts on the level with this name instead of the first scene.
+    pub fn level(mut self, name: &'static str) -> Self {
+        self.level = Some(name);
+        self
+    }
+
+    pub fn game_mode(mut self, game_mode: GameMode) -> Self {
+        self.game_mode = game_mode;
+        self
+    }
+
+    pub fn build(self) -> TestApp {
+        let mut app = App::new();
+
+        app.add_plugins((MinimalPlugins, StatesPlugin))
+            .insert_resource(TimeUpdateStrategy::ManualDuration(TICK))
+            .insert_state(GameState::MainMenu)
+            .add_plugins((
+                LevelPlugin,
+                ActionPlugin,
+                SimulationPlugin,
+                PlayerPlugin,
+                ChallengePlugin,
+                HintPlugin,
+                DelayedCommandPlugin,
+            ))
+            // Stand-ins for what the asset, settings and replay plugins provide
+            .insert_resource(ModelAssets {
+                player: Handle::default(),
+            })
+            .init_resource::<TileMesh>()
+            .init_resource::<TileMaterials>()
+            .insert_resource(self.game_mode)
+            .insert_resource(ChallengeState::new())
+            .init_resource::<ReplayMode>()
+            .init_resource::<TriggerCounts>()
+            .add_observer(
+                |_trigger: Trigger<LevelCompleted>, mut counts: ResMut<TriggerCounts>| {
+                    counts.levels_completed += 1;
+                },
+            )
+            .add_observer(
+                |_trigger: Trigger<Death>, mut counts: ResMut<TriggerCounts>| {
+                    counts.deaths += 1;
+                },
+            );
+
+        app.world_mut().spawn(ChallengePlugin::spawn_ui());
+
+        // Runs `Startup`, the level is only spawned once we're in game
+        app.update();
+
+        if let Some(name) = self.level {
+            let index = SCENES
+                .iter()
+                .position(|scene| matches!(scene, Scene::Level(level) if level.name == name))
+                .unwrap_or_else(|| panic!("there's no level named {name}"));
+
+            app.world_mut().trigger(LoadLevel(index));
+        }
+
+        app.world_mut()
+            .resource_mut::<NextState<GameState>>()
+            .set(GameState::InGame);
+        app.update();
+
+        TestApp { app }
+    }
+}
+
+impl TestApp {
+    pub fn builder() -> TestAppBuilder {
+        TestAppBuilder {
+            level: None,
+            game_mode: GameMode::Story,
+        }
+    }
+
+    pub fn world(&self) -> &World {
+        self.app.world()
+    }
+
+    pub fn world_mut(&mut self) -> &mut World {
+        self.app.world_mut()
+    }
+
+    pub fn trigger<E: Event>(&mut self, event: E) {
+        self.app.world_mut().trigger(event);
+    }
+
+    /// Replaces the plan and starts the simulation.
+    pub fn run_plan(&mut self, plan: impl Into<Vec<Action>>) {
+        self.trigger(LoadActionPlan(ActionPlan(plan.into())));
+        self.trigger(SimulationStart);
+    }
+
+    /// Moves virtual time forward by at least `secs`, one [`TICK`] per update.
+    pub fn advance(&mut self, secs: f32) {
+        let ticks = (secs / TICK.as_secs_f32()).ceil() as usize;
+
+        for _ in 0..ticks {
+            self.app.update();
+        }
+    }
+
+    pub fn level(&self) -> &Level {
+        self.world().resource::<Level>()
+    }
+
+    pub fn level_counter(&self) -> usize {
+        **self.world().resource::<LevelCounter>()
+    }
+
+    pub fn simulation_state(&self) -> SimulationState {
+        *self.world().resource::<State<SimulationState>>().get()
+    }
+
+    pub fn counts(&self) -> &TriggerCounts {
+        self.world().resource::<TriggerCounts>()
+    }
+
+    /// The challenge record for the current level.
+    pub fn record(&self) -> ChallengeRecord {
+        self.world()
+            .resource::<ChallengeState>()
+            .get(self.level().id)
+            .cloned()
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use crate::{
+        challenge::FewestCommands,
+        ui::hint::{HintLevel, Hints, ShowHint},
+    };
+
+    use Action::*;
+
+    #[test]
+    fn completing_a_level_moves_on() {
+        let mut app = TestApp::builder().level("Obstructions").build();
+        let counter = app.level_counter();
+
+        app.run_plan([Forward, Right]);
+        app.advance(1.);
+        assert_eq!(app.simulation_state(), SimulationState::Running);
+
+        // The sixth step lands on the finish
+        app.advance(2.);
+        assert_eq!(app.counts().levels_completed, 1);
+        assert_eq!(app.level_counter(), counter);
+
+        let record = app.record();
+        assert!(record.level_completed);
+        assert!(record.all_completed(app.level()));
+        assert_eq!(record.attempts, 1);
+        assert_eq!(record.fewest_commands, Some(2));
+        assert_eq!(record.fewest_steps, Some(6));
+
+        app.advance(2.);
+        assert_eq!(app.level_counter(), counter + 1);
+        assert_ne!(app.level().name, "Obstructions");
+        assert!(app.world().resource::<ActionPlan>().is_empty());
+        assert_eq!(app.simulation_state(), SimulationState::Stopped);
+    }
+
+    #[test]
+    fn dying_stops_the_simulation() {
+        let mut app = TestApp::builder().level("Obstructions").build();
+
+        app.run_plan([Backward]);
+        assert_eq!(app.counts().deaths, 1);
+
+        app.advance(2.5);
+        assert_eq!(app.simulation_state(), SimulationState::Stopped);
+        assert_eq!(app.counts().levels_completed, 0);
+        assert_eq!(app.record().deaths, 1);
+        assert!(!app.record().level_completed);
+    }
+
+    #[test]
+    fn challenges_stay_assisted_after_retrying_a_hinted_level() {
+        let mut app = TestApp::builder()
+            .level("Obstructions")
+            .game_mode(GameMode::Challenge)
+            .build();
+        let counter = app.level_counter();
+
+        app.trigger(ShowHint);
+        app.advance(0.5);
+        assert_eq!(app.world().resource::<Hints>().level, HintLevel::OnTrack);
+
+        // Too many commands for the challenge, so the level restarts and the hint goes away
+        app.run_plan([Forward, Forward, Right]);
+        app.advance(5.);
+        assert_eq!(app.level_counter(), counter);
+        assert_eq!(app.world().resource::<Hints>().level, HintLevel::None);
+
+        app.run_plan([Forward, Right]);
+        app.advance(5.);
+        assert_eq!(app.level_counter(), counter + 1);
+
+        let record = app.world().resource::<ChallengeState>()["obstructions"].clone();
+        assert_eq!(record.hints_used, 1);
+        assert!(record.is_assisted(&FewestCommands(2)));
+    }
+
+    #[test]
+    fn players_falling_together_are_one_death() {
+        let mut app = TestApp::builder().level("Mirror").build();
+
+        // Both players walk off the level on the first step
+        app.run_plan([Backward]);
+        assert_eq!(app.counts().deaths, 2);
+        assert_eq!(app.record().deaths, 1);
+
+        app.advance(2.5);
+        assert_eq!(app.simulation_state(), SimulationState::Stopped);
+
+        app.run_plan([Backward]);
+        assert_eq!(app.record().deaths, 2);
+        assert_eq!(app.record().attempts, 2);
+    }
+
+    #[test]
+    fn challenge_mode_repeats_levels_until_their_challenges_are_met() {
+        let mut app = TestApp::builder()
+            .level("Obstructions")
+            .game_mode(GameMode::Challenge)
+            .build();
+        let counter = app.level_counter();
+
+        app.run_plan([Forward, Forward, Right]);
+        app.advance(5.);
+        assert_eq!(app.counts().levels_completed, 1);
+        assert_eq!(app.level_counter(), counter);
+        assert!(app.record().level_completed);
+        assert!(!app.record().all_completed(app.level()));
+
+        app.run_plan([Forward, Right]);
+        app.advance(5.);
+        assert_eq!(app.counts().levels_completed, 2);
+        assert_eq!(app.level_counter(), counter + 1);
+    }
+}
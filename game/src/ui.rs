@@ -7,8 +7,13 @@ use constants::*;
 use controls::ControlsPlugin;
 use dialogue::DialoguePlugin;
 use end_screen::EndScreenPlugin;
+use focus::{FocusPlugin, FocusScope};
+use hint::HintPlugin;
 use main_menu::MainMenuPlugin;
+use schematic::SchematicPlugin;
 use settings::SettingsPlugin;
+use share::SharePlugin;
+use text_input::TextInputPlugin;
 
 use crate::{actions::AddAction, game_state::GameState, simulation::SimulationStart};
 
@@ -20,8 +25,13 @@ pub mod constants;
 pub mod controls;
 pub mod dialogue;
 pub mod end_screen;
+pub mod focus;
+pub mod hint;
 pub mod main_menu;
+pub mod schematic;
 pub mod settings;
+pub mod share;
+pub mod text_input;
 
 pub struct UIPlugin;
 
@@ -36,13 +46,24 @@ impl Plugin for UIPlugin {
             .add_plugins(DialoguePlugin)
             .add_plugins(EndScreenPlugin)
             .add_plugins(SettingsPlugin)
+            .add_plugins(FocusPlugin)
+            .add_plugins(TextInputPlugin)
+            .add_plugins(SharePlugin)
+            .add_plugins(HintPlugin)
+            .add_plugins(SchematicPlugin)
             .add_systems(OnExit(GameState::MainMenu), setup);
     }
 }
 
+/// The row holding the UI columns down the left of the screen.
+#[derive(Debug, Component)]
+pub struct UiRoot;
+
 fn setup(mut commands: Commands) {
     commands.spawn((
         Name::new("UI Root"),
+        UiRoot,
+        FocusScope::Base,
         Node {
             height: Val::Percent(100.0),
             flex_direction: FlexDirection::Row,
@@ -72,9 +93,14 @@ fn setup(mut commands: Commands) {
                         children![
                             ActionListPlugin::spawn_ui(),
                             ControlsPlugin::clear_button(),
+                            ControlsPlugin::undo_button(),
+                            ControlsPlugin::redo_button(),
                             ControlsPlugin::start_button(),
+                            SharePlugin::share_button(),
+                            HintPlugin::hint_button(),
                         ]
-                    )
+                    ),
+                    HintPlugin::spawn_ui(),
                 ]
             ),
             ChallengePlugin::spawn_ui()
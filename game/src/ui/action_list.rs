@@ -1,24 +1,41 @@
-use bevy::{ecs::spawn::SpawnWith, prelude::*};
+use bevy::{
+    ecs::spawn::SpawnWith,
+    prelude::*,
+    ui::{FocusPolicy, RelativeCursorPosition},
+    window::PrimaryWindow,
+};
 
 use crate::{
-    actions::{ActionPlan, RemoveAction},
+    actions::{Action, ActionPlan, InsertAction, MoveAction, RemoveAction, SwapActions},
     assets::IconAssets,
     game_state::GameState,
     level::Level,
+    replay::ReplayMode,
     simulation::{SimulationProgramCounter, SimulationState},
 };
 
-use super::{button, challenges::StepCount, constants::*, horizontal_line};
+use super::{button, challenges::StepCount, constants::*, focus::Focusable, horizontal_line};
 
 pub struct ActionListPlugin;
 
 impl Plugin for ActionListPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(
-            Update,
-            update_action_list.run_if(in_state(GameState::InGame)),
-        )
-        .add_systems(Update, reorder_button.run_if(in_state(GameState::InGame)));
+        app.insert_resource(ActionCursor::default())
+            .insert_resource(ActionDrag::default())
+            .add_systems(
+                Update,
+                update_action_list.run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(Update, reorder_button.run_if(in_state(GameState::InGame)))
+            .add_systems(
+                Update,
+                (start_drag, update_drag, end_drag)
+                    .chain()
+                    .before(update_action_list)
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(OnExit(GameState::InGame), cancel_drag)
+            .add_systems(Update, clamp_action_cursor);
     }
 }
 
@@ -29,6 +46,7 @@ impl ActionListPlugin {
     pub fn spawn_ui() -> impl Bundle {
         (
             ActionPlanUI,
+            RelativeCursorPosition::default(),
             Node {
                 // width: Val::Px(350.),
                 flex_direction: FlexDirection::Column,
@@ -44,6 +62,34 @@ impl ActionListPlugin {
     }
 }
 
+/// The entry of the action list selected for reordering and removal without a mouse.
+#[derive(Debug, Clone, Copy, Default, Resource, Deref, DerefMut)]
+pub struct ActionCursor(pub Option<usize>);
+
+/// Where an action being dragged onto the action list came from.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DragSource {
+    Row(usize),
+    Menu(Action),
+}
+
+/// A grip that starts a drag when pressed.
+#[derive(Debug, Clone, Copy, Component)]
+pub struct DragHandle(pub DragSource);
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource)]
+pub struct ActionDrag {
+    pub source: Option<DragSource>,
+    /// The index the dragged action will be inserted before, if it's dropped now.
+    pub target: Option<usize>,
+}
+
+#[derive(Debug, Component)]
+pub struct ActionRow;
+
+#[derive(Debug, Component)]
+pub struct DragGhost;
+
 #[derive(Debug)]
 pub enum ButtonType {
     Up,
@@ -68,12 +114,18 @@ fn update_action_list(
     simulation_state: Res<State<SimulationState>>,
     program_counter: Res<SimulationProgramCounter>,
     step_count: Res<StepCount>,
+    cursor: Res<ActionCursor>,
+    drag: Res<ActionDrag>,
+    replay: Res<ReplayMode>,
 ) {
     if !(action_plan.is_changed()
         || level.is_changed()
         || simulation_state.is_changed()
         || program_counter.is_changed()
-        || step_count.is_changed())
+        || step_count.is_changed()
+        || cursor.is_changed()
+        || drag.is_changed()
+        || replay.is_changed())
     {
         return;
     }
@@ -101,7 +153,8 @@ fn update_action_list(
                     ..default()
                 },
                 Text(format!(
-                    "max {max} command{plural}{trailing}",
+                    "{replay}max {max} command{plural}{trailing}",
+                    replay = if replay.is_active() { "replay; " } else { "" },
                     max = level.action_limit,
                     plural = if level.action_limit == 1 { "" } else { "s" },
                     trailing = if **step_count > 0 {
@@ -120,15 +173,28 @@ fn update_action_list(
         ])
         .with_children(|parent| {
             if action_plan.is_empty() {
-                parent.spawn((Text("No Commands".into()), TextColor(GHOST_TEXT_COLOR)));
+                let color = match drag.target {
+                    Some(_) => PRIMARY_TEXT_COLOR,
+                    None => GHOST_TEXT_COLOR,
+                };
+
+                parent.spawn((Text("No Commands".into()), TextColor(color)));
             }
 
             for (index, action) in action_plan.iter().enumerate() {
-                let prevent_interactions = simulation_state.get() != &SimulationState::Stopped;
+                let prevent_interactions =
+                    simulation_state.get() != &SimulationState::Stopped || replay.is_active();
 
-                let background_color = match program_counter.0 == index && prevent_interactions {
-                    true => GHOST_TEXT_COLOR,
-                    false => Color::NONE,
+                let background_color = if prevent_interactions {
+                    match program_counter.0 == index {
+                        true => GHOST_TEXT_COLOR,
+                        false => Color::NONE,
+                    }
+                } else {
+                    match **cursor == Some(index) {
+                        true => BUTTON_COLOR,
+                        false => Color::NONE,
+                    }
                 };
 
                 let up = icons.up.clone();
@@ -136,8 +202,15 @@ fn update_action_list(
 
                 let is_last = index == action_plan.len() - 1;
 
+                let text_color = match drag.source == Some(DragSource::Row(index)) {
+                    true => GHOST_TEXT_COLOR,
+                    false => PRIMARY_TEXT_COLOR,
+                };
+
                 parent
                     .spawn((
+                        ActionRow,
+                        RelativeCursorPosition::default(),
                         Node {
                             width: Val::Percent(100.),
                             min_height: Val::Px(40.),
@@ -176,6 +249,7 @@ fn update_action_list(
                                             } else {
                                                 Color::WHITE
                                             }),
+                                            Focusable,
                                             ReorderButton {
                                                 button_type: ButtonType::Up,
                                                 disabled: up_disabled,
@@ -198,6 +272,7 @@ fn update_action_list(
                                             } else {
                                                 Color::WHITE
                                             }),
+                                            Focusable,
                                             ReorderButton {
                                                 button_type: ButtonType::Down,
                                                 disabled: down_disabled,
@@ -209,6 +284,7 @@ fn update_action_list(
                             ),
                             (
                                 Text((*action).into()),
+                                TextColor(text_color),
                                 Node {
                                     flex_grow: 1.,
                                     ..default()
@@ -217,10 +293,29 @@ fn update_action_list(
                         ],
                     ))
                     .with_children(|row| {
+                        if drag.target == Some(index) {
+                            row.spawn(drop_indicator(Val::Px(-DROP_INDICATOR_OFFSET), Val::Auto));
+                        }
+
+                        if is_last && drag.target == Some(action_plan.len()) {
+                            row.spawn(drop_indicator(Val::Auto, Val::Px(-DROP_INDICATOR_OFFSET)));
+                        }
+
                         if prevent_interactions {
                             return;
                         }
 
+                        row.spawn((
+                            Button,
+                            DragHandle(DragSource::Row(index)),
+                            Node {
+                                width: Val::Px(24.),
+                                height: Val::Px(24.),
+                                ..default()
+                            },
+                            ImageNode::new(icons.drag.clone()),
+                        ));
+
                         row.spawn(
                             button::Button::builder()
                                 .icon(icons.remove.clone())
@@ -237,7 +332,7 @@ fn update_action_list(
 
 fn reorder_button(
     mut buttons: Query<(&ReorderButton, &Interaction, &mut ImageNode), Changed<Interaction>>,
-    mut action_plan: ResMut<ActionPlan>,
+    mut commands: Commands,
 ) {
     for (button, interaction, mut image) in &mut buttons {
         if button.disabled {
@@ -253,12 +348,185 @@ fn reorder_button(
             }
             Interaction::Pressed => match button.button_type {
                 ButtonType::Up => {
-                    action_plan.swap(button.index, button.index - 1);
+                    commands.trigger(SwapActions(button.index, button.index - 1));
                 }
                 ButtonType::Down => {
-                    action_plan.swap(button.index, button.index + 1);
+                    commands.trigger(SwapActions(button.index, button.index + 1));
                 }
             },
         }
     }
 }
+
+const DROP_INDICATOR_HEIGHT: f32 = 4.;
+
+// Centre the indicator in the gap between two rows
+const DROP_INDICATOR_OFFSET: f32 = (UI_CONTAINER_GAP + DROP_INDICATOR_HEIGHT) / 2.;
+
+/// A line drawn over the edge of a row, positioned absolutely so it doesn't move the rows around
+/// while they're being used to find the drop target.
+fn drop_indicator(top: Val, bottom: Val) -> impl Bundle {
+    (
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Px(DROP_INDICATOR_HEIGHT),
+            top,
+            bottom,
+            ..default()
+        },
+        BorderRadius::all(Val::Px(DROP_INDICATOR_HEIGHT / 2.)),
+        BackgroundColor(PRIMARY_TEXT_COLOR),
+        FocusPolicy::Pass,
+    )
+}
+
+fn start_drag(
+    mut commands: Commands,
+    mut handles: Query<(&DragHandle, &Interaction, &mut ImageNode), Changed<Interaction>>,
+    mut drag: ResMut<ActionDrag>,
+    action_plan: Res<ActionPlan>,
+    level: Res<Level>,
+    simulation_state: Res<State<SimulationState>>,
+    replay: Res<ReplayMode>,
+) {
+    for (handle, interaction, mut image) in &mut handles {
+        match interaction {
+            Interaction::None => {
+                image.color = Color::WHITE;
+            }
+            Interaction::Hovered => {
+                image.color = BUTTON_COLOR;
+            }
+            Interaction::Pressed => {
+                if drag.source.is_some()
+                    || simulation_state.get() != &SimulationState::Stopped
+                    || replay.is_active()
+                {
+                    continue;
+                }
+
+                let action = match handle.0 {
+                    DragSource::Row(index) => action_plan[index],
+                    DragSource::Menu(action) => {
+                        if action_plan.len() >= level.action_limit {
+                            continue;
+                        }
+
+                        action
+                    }
+                };
+
+                drag.source = Some(handle.0);
+
+                commands.spawn((
+                    DragGhost,
+                    Node {
+                        position_type: PositionType::Absolute,
+                        ..default()
+                    },
+                    Text(action.into()),
+                    TextColor(PRIMARY_TEXT_COLOR),
+                    GlobalZIndex(i32::MAX),
+                    FocusPolicy::Pass,
+                ));
+            }
+        }
+    }
+}
+
+fn update_drag(
+    mut drag: ResMut<ActionDrag>,
+    list: Query<&RelativeCursorPosition, With<ActionPlanUI>>,
+    rows: Query<&RelativeCursorPosition, With<ActionRow>>,
+    mut ghost: Query<&mut Node, With<DragGhost>>,
+    window: Query<&Window, With<PrimaryWindow>>,
+) {
+    if drag.source.is_none() {
+        return;
+    }
+
+    let cursor = window.single().ok().and_then(Window::cursor_position);
+
+    if let (Some(cursor), Ok(mut ghost)) = (cursor, ghost.single_mut()) {
+        ghost.left = Val::Px(cursor.x);
+        ghost.top = Val::Px(cursor.y);
+    }
+
+    let over_list = list.iter().any(RelativeCursorPosition::mouse_over);
+
+    // Rows are laid out top to bottom, so the drop index is the number of rows whose middle is
+    // above the cursor
+    let target = over_list.then(|| {
+        rows.iter()
+            .filter(|row| row.normalized.is_some_and(|position| position.y > 0.5))
+            .count()
+    });
+
+    drag.set_if_neq(ActionDrag {
+        source: drag.source,
+        target,
+    });
+}
+
+fn end_drag(
+    mut commands: Commands,
+    mouse: Res<ButtonInput<MouseButton>>,
+    simulation_state: Res<State<SimulationState>>,
+    mut drag: ResMut<ActionDrag>,
+    ghost: Query<Entity, With<DragGhost>>,
+) {
+    let Some(source) = drag.source else {
+        return;
+    };
+
+    let stopped = simulation_state.get() == &SimulationState::Stopped;
+
+    if stopped && !mouse.just_released(MouseButton::Left) {
+        return;
+    }
+
+    if let (true, Some(target)) = (stopped, drag.target) {
+        match source {
+            // The target index is counted before the row is taken out of the plan
+            DragSource::Row(from) => commands.trigger(MoveAction {
+                from,
+                to: if target > from { target - 1 } else { target },
+            }),
+            DragSource::Menu(action) => commands.trigger(InsertAction {
+                index: target,
+                action,
+            }),
+        }
+    }
+
+    for ghost in &ghost {
+        commands.entity(ghost).despawn();
+    }
+
+    *drag = ActionDrag::default();
+}
+
+fn cancel_drag(
+    mut commands: Commands,
+    mut drag: ResMut<ActionDrag>,
+    ghost: Query<Entity, With<DragGhost>>,
+) {
+    for ghost in &ghost {
+        commands.entity(ghost).despawn();
+    }
+
+    *drag = ActionDrag::default();
+}
+
+fn clamp_action_cursor(action_plan: Res<ActionPlan>, mut cursor: ResMut<ActionCursor>) {
+    if !action_plan.is_changed() {
+        return;
+    }
+
+    if let Some(index) = **cursor {
+        if index >= action_plan.len() {
+            **cursor = action_plan.len().checked_sub(1);
+        }
+    }
+}
@@ -1,8 +1,14 @@
 use bevy::prelude::*;
 
-use crate::{actions::ActionPlan, level::Level, simulation::SimulationState};
+use crate::{
+    actions::ActionPlan, assets::IconAssets, level::Level, replay::ReplayMode,
+    simulation::SimulationState,
+};
 
-use super::*;
+use super::{
+    action_list::{DragHandle, DragSource},
+    *,
+};
 
 pub struct ActionMenuPlugin;
 
@@ -41,6 +47,7 @@ pub struct ActionButton;
 fn update_available_actions(
     mut commands: Commands,
     level: Res<Level>,
+    icons: Res<IconAssets>,
     action_menu: Query<Entity, With<ActionMenuUI>>,
 ) -> Result {
     if !level.is_changed() {
@@ -71,13 +78,33 @@ fn update_available_actions(
                     for action in &level.actions {
                         let action = *action;
 
-                        action_row.spawn((
-                            ActionButton,
-                            button::Button::builder()
-                                .text(action.into())
-                                .on_click(move |commands| commands.trigger(AddAction(action)))
-                                .build(),
-                        ));
+                        action_row
+                            .spawn(Node {
+                                align_items: AlignItems::Center,
+                                ..default()
+                            })
+                            .with_children(|item| {
+                                item.spawn((
+                                    Button,
+                                    DragHandle(DragSource::Menu(action)),
+                                    Node {
+                                        width: Val::Px(24.),
+                                        height: Val::Px(24.),
+                                        ..default()
+                                    },
+                                    ImageNode::new(icons.drag.clone()),
+                                ));
+
+                                item.spawn((
+                                    ActionButton,
+                                    button::Button::builder()
+                                        .text(action.into())
+                                        .on_click(move |commands| {
+                                            commands.trigger(AddAction(action))
+                                        })
+                                        .build(),
+                                ));
+                            });
                     }
                 });
         });
@@ -89,15 +116,21 @@ fn plan_is_full(
     level: Res<Level>,
     action_plan: Res<ActionPlan>,
     simulation: Res<State<SimulationState>>,
+    replay: Res<ReplayMode>,
     mut buttons: Query<&mut button::Button, With<ActionButton>>,
 ) {
-    if !action_plan.is_changed() && !level.is_changed() && !simulation.is_changed() {
+    if !action_plan.is_changed()
+        && !level.is_changed()
+        && !simulation.is_changed()
+        && !replay.is_changed()
+    {
         return;
     }
 
     let full = action_plan.len() >= level.action_limit;
 
     for mut button in &mut buttons {
-        button.disabled = full || simulation.get() != &SimulationState::Stopped;
+        button.disabled =
+            full || simulation.get() != &SimulationState::Stopped || replay.is_active();
     }
 }
@@ -1,25 +1,35 @@
+use std::collections::HashMap;
+
 use bevy::{ecs::system::SystemParam, prelude::*};
 use serde::{Deserialize, Serialize};
 
 use crate::{
     actions::{Action, ActionPlan},
+    challenge::{Challenge, RunSummary},
+    game_state::unix_timestamp,
     level::{self, Level, LevelCounter, SCENES},
-    player::LevelCompleted,
-    simulation::SimulationStop,
+    player::{Death, LevelCompleted},
+    replay::ReplayMode,
+    simulation::{SimulationStart, SimulationStop},
 };
 
 use super::*;
 
+pub use crate::challenge::ChallengeRecord;
+
 pub struct ChallengePlugin;
 
 impl Plugin for ChallengePlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(StepCount::default())
+            .insert_resource(RunDied::default())
             .add_systems(
                 Update,
                 update_challenge_ui.run_if(in_state(GameState::InGame)),
             )
             .add_observer(update_challenges)
+            .add_observer(count_attempts)
+            .add_observer(count_deaths)
             .add_observer(count_steps)
             .add_observer(reset_steps);
     }
@@ -43,16 +53,9 @@ impl ChallengePlugin {
     }
 }
 
-#[derive(Debug, Clone, Default, Copy, Serialize, Deserialize)]
-pub struct ChallengeRecord {
-    pub commands: Option<bool>,
-    pub steps: Option<bool>,
-    pub waste: Option<bool>,
-    pub level_completed: bool,
-}
-
+/// Challenge records keyed by [`Level::id`].
 #[derive(Debug, Clone, Resource, Deref, DerefMut, Serialize, Deserialize)]
-pub struct ChallengeState(std::collections::HashMap<String, ChallengeRecord>);
+pub struct ChallengeState(HashMap<String, ChallengeRecord>);
 
 impl Default for ChallengeState {
     fn default() -> Self {
@@ -62,12 +65,14 @@ impl Default for ChallengeState {
                 .filter_map(|scene| {
                     if let level::Scene::Level(level) = scene {
                         Some((
-                            level.name.to_string(),
+                            level.id.to_string(),
                             ChallengeRecord {
-                                commands: level.command_challenge.is_some().then_some(false),
-                                steps: level.step_challenge.is_some().then_some(false),
-                                waste: level.waste_challenge.is_some().then_some(false),
-                                level_completed: false,
+                                challenges: level
+                                    .challenges
+                                    .iter()
+                                    .map(|challenge| (challenge.id(), false))
+                                    .collect(),
+                                ..default()
                             },
                         ))
                     } else {
@@ -96,7 +101,7 @@ impl ActiveChallenge<'_> {
         match SCENES.get(**self.level_counter)? {
             level::Scene::Level(level) => Some(
                 self.challenge_state
-                    .entry(level.name.to_string())
+                    .entry(level.id.to_string())
                     .or_default(),
             ),
             _ => None,
@@ -110,10 +115,49 @@ impl ActiveChallenge<'_> {
 
 #[derive(Debug, Clone, Copy, Resource, Default, Deref, DerefMut)]
 pub struct StepCount(usize);
+
+/// Whether anyone has died this run, players falling together only count as one death.
+#[derive(Debug, Clone, Copy, Resource, Default, Deref, DerefMut)]
+struct RunDied(bool);
+
 fn count_steps(_trigger: Trigger<Action>, mut step_count: ResMut<StepCount>) {
     **step_count += 1;
 }
 
+fn count_attempts(
+    _trigger: Trigger<SimulationStart>,
+    mut challenge: ActiveChallenge,
+    replay: Res<ReplayMode>,
+    mut died: ResMut<RunDied>,
+) {
+    **died = false;
+
+    if replay.is_active() {
+        return;
+    }
+
+    if let Some(record) = challenge.get_record_mut() {
+        record.attempts += 1;
+    }
+}
+
+fn count_deaths(
+    _trigger: Trigger<Death>,
+    mut challenge: ActiveChallenge,
+    replay: Res<ReplayMode>,
+    mut died: ResMut<RunDied>,
+) {
+    if replay.is_active() || **died {
+        return;
+    }
+
+    **died = true;
+
+    if let Some(record) = challenge.get_record_mut() {
+        record.deaths += 1;
+    }
+}
+
 fn reset_steps(_trigger: Trigger<SimulationStop>, mut step_count: ResMut<StepCount>) {
     **step_count = 0;
 }
@@ -124,31 +168,26 @@ fn update_challenges(
     action_plan: Res<ActionPlan>,
     step_count: Res<StepCount>,
     level: Res<Level>,
+    replay: Res<ReplayMode>,
 ) {
-    if let Some(challenge) = challenge.get_record_mut() {
-        challenge.level_completed = true;
-
-        if let Some(command_challenge) = level.command_challenge {
-            if action_plan.len() <= command_challenge {
-                if let Some(completed) = &mut challenge.commands {
-                    *completed = true;
-                }
-            }
-        }
+    if replay.is_active() {
+        return;
+    }
 
-        if let Some(step_challenge) = level.step_challenge {
-            if **step_count <= step_challenge {
-                if let Some(completed) = &mut challenge.steps {
-                    *completed = true;
-                }
-            }
-        }
+    let run = RunSummary {
+        plan: action_plan.0.clone(),
+        steps: **step_count,
+    };
+
+    if let Some(record) = challenge.get_record_mut() {
+        record.record_completion(run.commands(), run.steps, unix_timestamp());
 
-        if let Some(waste_challenge) = level.waste_challenge {
-            if **step_count >= waste_challenge {
-                if let Some(completed) = &mut challenge.waste {
-                    *completed = true;
-                }
+        // From the record rather than the hints on screen, which are lost when the level restarts
+        let assisted = record.hints_used > 0;
+
+        for level_challenge in &level.challenges {
+            if level_challenge.evaluate(&run) {
+                record.complete_challenge(level_challenge.id(), assisted);
             }
         }
     }
@@ -164,7 +203,7 @@ fn update_challenge_ui(
         return;
     }
 
-    let challenge = challenge.get_record_mut().cloned().unwrap_or_default();
+    let record = challenge.get_record_mut().cloned().unwrap_or_default();
 
     commands
         .entity(query.single().unwrap())
@@ -172,73 +211,81 @@ fn update_challenge_ui(
         .with_children(|container| {
             let success_color = Color::srgba_u8(0x0c, 0xc4, 0x0f, 0xdd);
 
-            let mut spawn_challenge_tracker = |text: String, completed: bool| {
-                container
-                    .spawn((
-                        Node {
-                            padding: UiRect::axes(
-                                Val::Px(UI_CONTAINER_GAP * 2.),
-                                Val::Px(UI_CONTAINER_GAP),
-                            ),
-                            justify_content: JustifyContent::Start,
-                            align_items: AlignItems::Center,
-                            column_gap: Val::Px(UI_CONTAINER_GAP),
-                            ..default()
-                        },
-                        BorderRadius::all(Val::Px(UI_CONTAINER_RADIUS)),
-                        BorderColor(if completed {
-                            success_color
-                        } else {
-                            Color::default()
-                        }),
-                        BackgroundColor(UI_BACKGROUND_COLOR),
-                    ))
-                    .with_children(|container| {
-                        container.spawn((
+            let mut spawn_challenge_tracker =
+                |text: String, completed: bool, best: Option<String>| {
+                    container
+                        .spawn((
                             Node {
-                                width: Val::Px(24.),
-                                height: Val::Px(24.),
-                                border: UiRect::all(Val::Px(2.)),
+                                padding: UiRect::axes(
+                                    Val::Px(UI_CONTAINER_GAP * 2.),
+                                    Val::Px(UI_CONTAINER_GAP),
+                                ),
+                                justify_content: JustifyContent::Start,
+                                align_items: AlignItems::Center,
+                                column_gap: Val::Px(UI_CONTAINER_GAP),
                                 ..default()
                             },
-                            BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS)),
-                            BorderColor(PRIMARY_TEXT_COLOR),
-                            BackgroundColor(if completed {
+                            BorderRadius::all(Val::Px(UI_CONTAINER_RADIUS)),
+                            BorderColor(if completed {
                                 success_color
                             } else {
-                                Color::NONE
+                                Color::default()
                             }),
-                        ));
+                            BackgroundColor(UI_BACKGROUND_COLOR),
+                        ))
+                        .with_children(|container| {
+                            container.spawn((
+                                Node {
+                                    width: Val::Px(24.),
+                                    height: Val::Px(24.),
+                                    border: UiRect::all(Val::Px(2.)),
+                                    ..default()
+                                },
+                                BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS)),
+                                BorderColor(PRIMARY_TEXT_COLOR),
+                                BackgroundColor(if completed {
+                                    success_color
+                                } else {
+                                    Color::NONE
+                                }),
+                            ));
 
-                        container.spawn((
-                            Text(text.into()),
-                            TextColor(if completed {
-                                GHOST_TEXT_COLOR
-                            } else {
-                                PRIMARY_TEXT_COLOR
-                            }),
-                        ));
-                    });
-            };
+                            container.spawn((
+                                Text(text.into()),
+                                TextColor(if completed {
+                                    GHOST_TEXT_COLOR
+                                } else {
+                                    PRIMARY_TEXT_COLOR
+                                }),
+                            ));
 
-            if let Some(steps) = level.step_challenge {
-                spawn_challenge_tracker(
-                    format!("Alacrity: Take {steps} or fewer steps"),
-                    challenge.steps.unwrap_or_default(),
-                );
-            }
+                            if let Some(best) = best {
+                                container.spawn((
+                                    Text(best),
+                                    TextColor(GHOST_TEXT_COLOR),
+                                    TextFont {
+                                        font_size: 16.,
+                                        ..default()
+                                    },
+                                ));
+                            }
+                        });
+                };
 
-            if let Some(commands) = level.command_challenge {
-                spawn_challenge_tracker(
-                    format!("Parsimony: Use {commands} or fewer commands"),
-                    challenge.commands.unwrap_or_default(),
-                );
-            }
+            for level_challenge in &level.challenges {
+                let note = match (
+                    level_challenge.best(&record),
+                    record.is_assisted(&**level_challenge),
+                ) {
+                    (Some(best), true) => Some(format!("{best}, assisted")),
+                    (None, true) => Some("assisted".into()),
+                    (best, false) => best,
+                };
 
-            if let Some(steps) = level.waste_challenge {
                 spawn_challenge_tracker(
-                    format!("Circuity: Take {steps} or more steps"),
-                    challenge.waste.unwrap_or_default(),
+                    level_challenge.description(),
+                    record.is_completed(&**level_challenge),
+                    note,
                 );
             }
         });
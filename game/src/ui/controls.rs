@@ -1,8 +1,9 @@
 use bevy::prelude::*;
 
 use crate::{
-    actions::{ActionPlan, ResetActionPlan},
+    actions::{ActionHistory, ActionPlan, Redo, ResetActionPlan, Undo},
     player::SpawnPlayer,
+    replay::ReplayMode,
     simulation::{SimulationState, SimulationStop},
 };
 
@@ -12,7 +13,8 @@ pub struct ControlsPlugin;
 
 impl Plugin for ControlsPlugin {
     fn build(&self, app: &mut App) {
-        app.add_systems(Update, update_control_state);
+        app.add_systems(Update, update_control_state)
+            .add_systems(Update, update_history_state);
     }
 }
 
@@ -22,6 +24,12 @@ pub struct PlayButton;
 #[derive(Debug, Component)]
 pub struct ResetButton;
 
+#[derive(Debug, Component)]
+pub struct UndoButton;
+
+#[derive(Debug, Component)]
+pub struct RedoButton;
+
 impl ControlsPlugin {
     pub fn clear_button() -> impl Bundle {
         (
@@ -47,11 +55,34 @@ impl ControlsPlugin {
             PlayButton,
         )
     }
+
+    pub fn undo_button() -> impl Bundle {
+        (
+            button::Button::builder()
+                .text("Undo".into())
+                .on_click(|commands| commands.trigger(Undo))
+                .disabled()
+                .build(),
+            UndoButton,
+        )
+    }
+
+    pub fn redo_button() -> impl Bundle {
+        (
+            button::Button::builder()
+                .text("Redo".into())
+                .on_click(|commands| commands.trigger(Redo))
+                .disabled()
+                .build(),
+            RedoButton,
+        )
+    }
 }
 
 fn update_control_state(
     action_plan: Res<ActionPlan>,
     simulation_state: Res<State<SimulationState>>,
+    replay: Res<ReplayMode>,
     mut play_button: Query<&mut button::Button, (With<PlayButton>, Without<ResetButton>)>,
     mut reset_button: Query<
         (&mut button::Button, &Children),
@@ -59,12 +90,14 @@ fn update_control_state(
     >,
     mut button_text: Query<&mut Text>,
 ) {
-    if !(action_plan.is_changed() || simulation_state.is_changed()) {
+    if !(action_plan.is_changed() || simulation_state.is_changed() || replay.is_changed()) {
         return;
     }
 
     for mut button in &mut play_button {
-        button.disabled = action_plan.is_empty() || *simulation_state != SimulationState::Stopped;
+        button.disabled = action_plan.is_empty()
+            || *simulation_state != SimulationState::Stopped
+            || replay.is_active();
     }
 
     for (mut button, children) in &mut reset_button {
@@ -83,7 +116,7 @@ fn update_control_state(
                 button.disabled = true;
             }
             SimulationState::Stopped => {
-                button.disabled = action_plan.len() == 0;
+                button.disabled = action_plan.len() == 0 || replay.is_active();
                 **text = "Clear".into();
                 button.on_click = Box::new(|commands| {
                     commands.trigger(ResetActionPlan);
@@ -92,3 +125,25 @@ fn update_control_state(
         }
     }
 }
+
+fn update_history_state(
+    history: Res<ActionHistory>,
+    simulation_state: Res<State<SimulationState>>,
+    replay: Res<ReplayMode>,
+    mut undo_button: Query<&mut button::Button, (With<UndoButton>, Without<RedoButton>)>,
+    mut redo_button: Query<&mut button::Button, (With<RedoButton>, Without<UndoButton>)>,
+) {
+    if !(history.is_changed() || simulation_state.is_changed() || replay.is_changed()) {
+        return;
+    }
+
+    let stopped = *simulation_state == SimulationState::Stopped && !replay.is_active();
+
+    for mut button in &mut undo_button {
+        button.disabled = !(stopped && history.can_undo());
+    }
+
+    for mut button in &mut redo_button {
+        button.disabled = !(stopped && history.can_redo());
+    }
+}
@@ -0,0 +1,216 @@
+use bevy::prelude::*;
+
+use super::{button, constants::PRIMARY_TEXT_COLOR};
+
+pub struct FocusPlugin;
+
+impl Plugin for FocusPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Focus::default())
+            .add_systems(Update, clear_stale_focus)
+            .add_observer(move_focus)
+            .add_observer(activate_focus)
+            .add_observer(clear_focus);
+    }
+}
+
+/// Marks an interactable node that isn't a [`button::Button`] as reachable by focus navigation.
+#[derive(Debug, Component)]
+pub struct Focusable;
+
+/// While any scope exists, focus navigation is restricted to the descendants of the scopes on the
+/// highest layer, e.g. a modal panel over the in-game UI.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Component)]
+pub enum FocusScope {
+    /// The UI on screen throughout a level.
+    Base,
+    /// Panels opened over the rest of the UI, which keep focus to themselves.
+    Modal,
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct Focus {
+    pub focused: Option<Entity>,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Event)]
+pub enum MoveFocus {
+    Up,
+    Down,
+    Left,
+    Right,
+}
+
+impl MoveFocus {
+    fn direction(self) -> Vec2 {
+        // UI space has y pointing down the screen
+        match self {
+            MoveFocus::Up => Vec2::NEG_Y,
+            MoveFocus::Down => Vec2::Y,
+            MoveFocus::Left => Vec2::NEG_X,
+            MoveFocus::Right => Vec2::X,
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ActivateFocus;
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ClearFocus;
+
+const FOCUS_OUTLINE_WIDTH: f32 = 3.;
+const FOCUS_OUTLINE_OFFSET: f32 = 2.;
+
+fn in_scope(
+    entity: Entity,
+    layer: FocusScope,
+    scopes: &Query<&FocusScope>,
+    child_of: &Query<&ChildOf>,
+) -> bool {
+    if scopes.get(entity).is_ok_and(|scope| *scope == layer) {
+        return true;
+    }
+
+    match child_of.get(entity) {
+        Ok(parent) => in_scope(parent.parent(), layer, scopes, child_of),
+        Err(_) => false,
+    }
+}
+
+fn move_focus(
+    trigger: Trigger<MoveFocus>,
+    mut commands: Commands,
+    mut focus: ResMut<Focus>,
+    candidates: Query<
+        (
+            Entity,
+            &GlobalTransform,
+            &InheritedVisibility,
+            Option<&button::Button>,
+        ),
+        Or<(With<button::Button>, With<Focusable>)>,
+    >,
+    scopes: Query<&FocusScope>,
+    child_of: Query<&ChildOf>,
+) {
+    let layer = scopes.iter().max().copied();
+
+    let candidates = candidates
+        .iter()
+        .filter(|(_, _, visibility, button)| {
+            visibility.get() && !button.is_some_and(|button| button.disabled)
+        })
+        .filter(|(entity, ..)| {
+            layer.is_none_or(|layer| in_scope(*entity, layer, &scopes, &child_of))
+        })
+        .map(|(entity, transform, ..)| (entity, transform.translation().truncate()))
+        .collect::<Vec<_>>();
+
+    let current = focus
+        .focused
+        .and_then(|focused| candidates.iter().find(|(entity, _)| *entity == focused));
+
+    let next = match current {
+        // Nothing focused yet, start from the top left
+        None => candidates
+            .iter()
+            .min_by(|(_, a), (_, b)| a.y.total_cmp(&b.y).then(a.x.total_cmp(&b.x)))
+            .map(|(entity, _)| *entity),
+        Some((current, origin)) => {
+            let direction = trigger.event().direction();
+
+            candidates
+                .iter()
+                .filter(|(entity, _)| entity != current)
+                .filter_map(|(entity, position)| {
+                    let delta = *position - *origin;
+                    let along = delta.dot(direction);
+
+                    if along <= 0. {
+                        return None;
+                    }
+
+                    // Prefer candidates that are in line with the direction of travel
+                    let across = (delta - along * direction).length();
+                    Some((*entity, along + 2. * across))
+                })
+                .min_by(|(_, a), (_, b)| a.total_cmp(b))
+                .map(|(entity, _)| entity)
+        }
+    };
+
+    let Some(next) = next else {
+        return;
+    };
+
+    set_focus(&mut commands, &mut focus, Some(next));
+}
+
+fn set_focus(commands: &mut Commands, focus: &mut Focus, next: Option<Entity>) {
+    if focus.focused == next {
+        return;
+    }
+
+    if let Some(previous) = focus.focused {
+        if let Ok(mut entity) = commands.get_entity(previous) {
+            entity.remove::<Outline>();
+        }
+    }
+
+    if let Some(next) = next {
+        commands.entity(next).insert(Outline::new(
+            Val::Px(FOCUS_OUTLINE_WIDTH),
+            Val::Px(FOCUS_OUTLINE_OFFSET),
+            PRIMARY_TEXT_COLOR,
+        ));
+    }
+
+    focus.focused = next;
+}
+
+fn activate_focus(
+    _trigger: Trigger<ActivateFocus>,
+    mut commands: Commands,
+    focus: Res<Focus>,
+    mut focusables: Query<(Option<&button::Button>, Option<&mut Interaction>)>,
+) {
+    let Some((button, interaction)) = focus
+        .focused
+        .and_then(|focused| focusables.get_mut(focused).ok())
+    else {
+        return;
+    };
+
+    match (button, interaction) {
+        (Some(button), _) => {
+            if !button.disabled {
+                (button.on_click)(&mut commands);
+            }
+        }
+        // Other focusables react to a change into the pressed state, exactly as they would for a
+        // mouse click, the UI focus system resets it on the next mouse release
+        (None, Some(mut interaction)) => *interaction = Interaction::Pressed,
+        (None, None) => {}
+    }
+}
+
+fn clear_stale_focus(
+    mut commands: Commands,
+    mut focus: ResMut<Focus>,
+    visibility: Query<&InheritedVisibility>,
+) {
+    let Some(focused) = focus.focused else {
+        return;
+    };
+
+    if visibility.get(focused).is_ok_and(|visible| visible.get()) {
+        return;
+    }
+
+    set_focus(&mut commands, &mut focus, None);
+}
+
+fn clear_focus(_trigger: Trigger<ClearFocus>, mut commands: Commands, mut focus: ResMut<Focus>) {
+    set_focus(&mut commands, &mut focus, None);
+}
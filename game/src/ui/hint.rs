@@ -0,0 +1,217 @@
+use bevy::{
+    prelude::*,
+    tasks::{AsyncComputeTaskPool, Task, block_on, futures_lite::future},
+};
+
+use crate::{
+    actions::{Action, ActionPlan},
+    level::Level,
+    replay::ReplayMode,
+    solver::{Solution, enumerate_solutions, plan_hint, smallest_solutions},
+};
+
+use super::{challenges::ActiveChallenge, *};
+
+pub struct HintPlugin;
+
+impl Plugin for HintPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(Hints::default())
+            .add_systems(
+                Update,
+                (reset_hints, poll_hint_search, update_hint_text)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_observer(show_hint);
+    }
+}
+
+/// Reveals the next, more helpful, hint for the current plan.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct ShowHint;
+
+/// How much help has been given on the current level, each hint gives away more than the last.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum HintLevel {
+    #[default]
+    None,
+    /// Whether the plan can still become a solution.
+    OnTrack,
+    NextCommand,
+    Solution,
+}
+
+impl HintLevel {
+    fn next(self) -> Self {
+        match self {
+            HintLevel::None => HintLevel::OnTrack,
+            HintLevel::OnTrack => HintLevel::NextCommand,
+            HintLevel::NextCommand | HintLevel::Solution => HintLevel::Solution,
+        }
+    }
+}
+
+#[derive(Debug, Default, Resource)]
+pub struct Hints {
+    pub level: HintLevel,
+    pub message: String,
+    /// Solved the first time a hint is asked for on the level.
+    solutions: Solutions,
+}
+
+/// The search can take a while on bigger levels, so it runs off the main thread.
+#[derive(Debug, Default)]
+enum Solutions {
+    #[default]
+    Unsolved,
+    Solving(Task<Vec<Solution>>),
+    Solved(Vec<Solution>),
+}
+
+#[derive(Debug, Component)]
+pub struct HintText;
+
+impl HintPlugin {
+    pub fn hint_button() -> impl Bundle {
+        button::Button::builder()
+            .text("Hint".into())
+            .on_click(|commands| commands.trigger(ShowHint))
+            .build()
+    }
+
+    pub fn spawn_ui() -> impl Bundle {
+        (
+            HintText,
+            Text::default(),
+            TextColor(PRIMARY_TEXT_COLOR),
+            TextFont {
+                font_size: 16.,
+                ..default()
+            },
+        )
+    }
+}
+
+fn commands_text(count: usize) -> String {
+    match count {
+        1 => "the last command".into(),
+        count => format!("the last {count} commands"),
+    }
+}
+
+fn plan_text(plan: &[Action]) -> String {
+    plan.iter()
+        .map(|action| String::from(*action))
+        .collect::<Vec<_>>()
+        .join(", ")
+}
+
+fn hint_message(level: HintLevel, solutions: &[Solution], plan: &[Action]) -> String {
+    if solutions.is_empty() {
+        return "There's no solution within the command limit".into();
+    }
+
+    let hint = plan_hint(solutions, plan);
+
+    if hint.solves && level != HintLevel::Solution {
+        return "Your plan already works, press Start".into();
+    }
+
+    match level {
+        HintLevel::None => String::new(),
+        HintLevel::OnTrack if hint.on_track() => {
+            "So far so good, your plan can still become a solution".into()
+        }
+        HintLevel::OnTrack => format!(
+            "No solution starts like this, try removing {}",
+            commands_text(hint.extra_commands)
+        ),
+        HintLevel::NextCommand => match (hint.on_track(), hint.next) {
+            (true, Some(next)) => format!("Try {} next", String::from(next)),
+            (false, Some(next)) => format!(
+                "Remove {}, then try {}",
+                commands_text(hint.extra_commands),
+                String::from(next)
+            ),
+            (_, None) => format!("Remove {}", commands_text(hint.extra_commands)),
+        },
+        HintLevel::Solution => format!(
+            "One of the shortest solutions is {}",
+            plan_text(&smallest_solutions(solutions)[0].path)
+        ),
+    }
+}
+
+fn show_hint(
+    _trigger: Trigger<ShowHint>,
+    level: Res<Level>,
+    action_plan: Res<ActionPlan>,
+    replay: Res<ReplayMode>,
+    mut hints: ResMut<Hints>,
+    mut challenge: ActiveChallenge,
+) {
+    if replay.is_active() {
+        return;
+    }
+
+    let hints = &mut *hints;
+
+    // The hint being worked out will show once the search is done
+    if matches!(hints.solutions, Solutions::Solving(_)) {
+        return;
+    }
+
+    hints.level = hints.level.next();
+
+    if let Some(record) = challenge.get_record_mut() {
+        record.hints_used += 1;
+    }
+
+    match &hints.solutions {
+        Solutions::Solved(solutions) => {
+            hints.message = hint_message(hints.level, solutions, &action_plan.0);
+        }
+        Solutions::Unsolved | Solutions::Solving(_) => {
+            let level = level.clone();
+            hints.solutions = Solutions::Solving(
+                AsyncComputeTaskPool::get().spawn(async move { enumerate_solutions(&level) }),
+            );
+            hints.message = "Thinking...".into();
+        }
+    }
+}
+
+fn poll_hint_search(action_plan: Res<ActionPlan>, mut hints: ResMut<Hints>) {
+    let Solutions::Solving(task) = &mut hints.solutions else {
+        return;
+    };
+
+    let Some(solutions) = block_on(future::poll_once(task)) else {
+        return;
+    };
+
+    hints.message = hint_message(hints.level, &solutions, &action_plan.0);
+    hints.solutions = Solutions::Solved(solutions);
+}
+
+/// Dropping a search that's still running cancels it.
+fn reset_hints(level: Res<Level>, mut hints: ResMut<Hints>) {
+    if level.is_changed() {
+        *hints = Hints::default();
+    }
+}
+
+fn update_hint_text(
+    hints: Res<Hints>,
+    mut texts: Query<&mut Text, With<HintText>>,
+    added: Query<(), Added<HintText>>,
+) {
+    if !hints.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.0.clone_from(&hints.message);
+    }
+}
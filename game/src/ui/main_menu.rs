@@ -1,8 +1,18 @@
 use bevy::prelude::*;
 
-use crate::delayed_command::{DelayedCommand, DelayedCommandExt};
+use crate::{
+    delayed_command::{DelayedCommand, DelayedCommandExt},
+    profile::{
+        ApplyImport, CancelImport, CreateProfile, DeleteProfile, ExportSave, ImportSave,
+        MAX_PROFILE_NAME_LENGTH, PendingImport, Profiles, RenameProfile, SwitchProfile,
+        TransferStatus,
+    },
+};
 
-use super::*;
+use super::{
+    text_input::{TextInput, TextInputFocus, TextInputSubmit, text_input},
+    *,
+};
 
 pub struct MainMenuPlugin;
 
@@ -13,6 +23,13 @@ impl Plugin for MainMenuPlugin {
             .add_observer(remove_ui)
             .add_observer(spawn_main_menu)
             .add_observer(refuse)
+            .add_observer(submit_profile_name)
+            .add_observer(submit_profile_name_input)
+            .insert_resource(ProfileMenu::default())
+            .add_systems(
+                Update,
+                update_profile_panel.run_if(in_state(GameState::MainMenu)),
+            )
             .add_systems(OnExit(GameState::MainMenu), destroy);
     }
 }
@@ -38,24 +55,42 @@ fn spawn_main_menu(_trigger: Trigger<SpawnMainMenu>, mut commands: Commands) {
             Node {
                 height: Val::Percent(100.0),
                 width: Val::Percent(100.0),
+                flex_direction: FlexDirection::Column,
                 justify_content: JustifyContent::Center,
-                column_gap: Val::Px(UI_CONTAINER_GAP),
+                row_gap: Val::Px(UI_CONTAINER_GAP * 2.),
                 align_items: AlignItems::Center,
                 ..default()
             },
             children![
-                button::Button::builder()
-                    .text("Begin Cycle No. 4,815,162,342".into())
-                    .on_click(|commands| commands.trigger(StartGame))
-                    .build(),
-                button::Button::builder()
-                    .text("Disobey".into())
-                    .background_color(BUTTON_CANCEL_COLOR)
-                    .on_click(|commands| {
-                        commands.trigger(RemoveUI);
-                        commands.trigger(Refuse);
-                    })
-                    .build()
+                (
+                    Node {
+                        column_gap: Val::Px(UI_CONTAINER_GAP),
+                        ..default()
+                    },
+                    children![
+                        button::Button::builder()
+                            .text("Begin Cycle No. 4,815,162,342".into())
+                            .on_click(|commands| commands.trigger(StartGame))
+                            .build(),
+                        button::Button::builder()
+                            .text("Disobey".into())
+                            .background_color(BUTTON_CANCEL_COLOR)
+                            .on_click(|commands| {
+                                commands.trigger(RemoveUI);
+                                commands.trigger(Refuse);
+                            })
+                            .build()
+                    ],
+                ),
+                (
+                    ProfilePanel,
+                    Node {
+                        flex_direction: FlexDirection::Column,
+                        align_items: AlignItems::Center,
+                        row_gap: Val::Px(UI_CONTAINER_GAP),
+                        ..default()
+                    },
+                ),
             ],
         ));
     });
@@ -63,6 +98,7 @@ fn spawn_main_menu(_trigger: Trigger<SpawnMainMenu>, mut commands: Commands) {
 
 fn destroy(mut commands: Commands) {
     commands.trigger(RemoveUI);
+    commands.insert_resource(ProfileMenu::Picking);
 }
 
 #[derive(Debug, Event)]
@@ -90,3 +126,261 @@ fn remove_ui(
 fn start_game(_trigger: Trigger<StartGame>, mut next_state: ResMut<NextState<GameState>>) {
     next_state.set(GameState::InGame);
 }
+
+/// Lists the profiles under the main menu buttons, rebuilt whenever they change.
+#[derive(Debug, Component)]
+struct ProfilePanel;
+
+#[derive(Debug, Component)]
+struct ProfileNameInput;
+
+/// What the profile panel is showing.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Resource)]
+enum ProfileMenu {
+    #[default]
+    Picking,
+    Renaming,
+    ConfirmingDelete,
+}
+
+/// Renames the active profile to whatever's in the name input.
+#[derive(Debug, Clone, Copy, Event)]
+struct SubmitProfileName;
+
+fn update_profile_panel(
+    mut commands: Commands,
+    profiles: Res<Profiles>,
+    menu: Res<ProfileMenu>,
+    pending_import: Res<PendingImport>,
+    status: Res<TransferStatus>,
+    mut focus: ResMut<TextInputFocus>,
+    panels: Query<(Entity, Ref<ProfilePanel>)>,
+) {
+    for (panel, marker) in &panels {
+        if !(marker.is_added()
+            || profiles.is_changed()
+            || menu.is_changed()
+            || pending_import.is_changed()
+            || status.is_changed())
+        {
+            continue;
+        }
+
+        let active = profiles.active().clone();
+
+        commands.entity(panel).despawn_related::<Children>();
+
+        if let Some(import) = &**pending_import {
+            commands.entity(panel).with_children(|parent| {
+                parent.spawn((
+                    Text(format!(
+                        "Import a save with {} levels completed into {}?",
+                        import.levels_completed(),
+                        active.name
+                    )),
+                    TextColor(PRIMARY_TEXT_COLOR),
+                    TextFont {
+                        font_size: 16.,
+                        ..default()
+                    },
+                ));
+                parent.spawn((
+                    Node {
+                        column_gap: Val::Px(UI_CONTAINER_GAP),
+                        ..default()
+                    },
+                    children![
+                        button::Button::builder()
+                            .text("Merge".into())
+                            .background_color(BUTTON_SUCCESS_COLOR)
+                            .on_click(|commands| commands.trigger(ApplyImport::Merge))
+                            .build(),
+                        button::Button::builder()
+                            .text("Replace".into())
+                            .background_color(BUTTON_CANCEL_COLOR)
+                            .on_click(|commands| commands.trigger(ApplyImport::Replace))
+                            .build(),
+                        button::Button::builder()
+                            .text("Cancel".into())
+                            .on_click(|commands| commands.trigger(CancelImport))
+                            .build(),
+                    ],
+                ));
+            });
+
+            continue;
+        }
+
+        let heading = commands
+            .spawn((
+                Text(match *menu {
+                    ProfileMenu::Picking => "Profile".into(),
+                    ProfileMenu::Renaming => format!("Rename {}", active.name),
+                    ProfileMenu::ConfirmingDelete => {
+                        format!("Delete {} and all of its progress?", active.name)
+                    }
+                }),
+                TextColor(PRIMARY_TEXT_COLOR),
+                TextFont {
+                    font_size: 16.,
+                    ..default()
+                },
+            ))
+            .id();
+
+        let row = commands
+            .spawn(Node {
+                column_gap: Val::Px(UI_CONTAINER_GAP),
+                ..default()
+            })
+            .id();
+
+        match *menu {
+            ProfileMenu::Picking => {
+                for profile in profiles.iter() {
+                    let id = profile.id;
+                    let mut button = button::Button::builder()
+                        .text(profile.name.clone())
+                        .on_click(move |commands| commands.trigger(SwitchProfile(id)));
+
+                    if id == active.id {
+                        button = button.background_color(BUTTON_SUCCESS_COLOR);
+                    }
+
+                    commands.entity(row).with_child(button.build());
+                }
+
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("New".into())
+                        .on_click(|commands| commands.trigger(CreateProfile))
+                        .build(),
+                );
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("Rename".into())
+                        .on_click(|commands| commands.insert_resource(ProfileMenu::Renaming))
+                        .build(),
+                );
+
+                if profiles.len() > 1 {
+                    commands.entity(row).with_child(
+                        button::Button::builder()
+                            .text("Delete".into())
+                            .background_color(BUTTON_CANCEL_COLOR)
+                            .on_click(|commands| {
+                                commands.insert_resource(ProfileMenu::ConfirmingDelete)
+                            })
+                            .build(),
+                    );
+                }
+            }
+            ProfileMenu::Renaming => {
+                let input = commands
+                    .spawn((
+                        ProfileNameInput,
+                        text_input(TextInput {
+                            max_length: MAX_PROFILE_NAME_LENGTH,
+                            ..TextInput::new(active.name.clone())
+                        }),
+                    ))
+                    .id();
+
+                commands.entity(row).add_child(input);
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("Save".into())
+                        .background_color(BUTTON_SUCCESS_COLOR)
+                        .on_click(|commands| commands.trigger(SubmitProfileName))
+                        .build(),
+                );
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("Cancel".into())
+                        .on_click(|commands| commands.insert_resource(ProfileMenu::Picking))
+                        .build(),
+                );
+
+                **focus = Some(input);
+            }
+            ProfileMenu::ConfirmingDelete => {
+                let id = active.id;
+
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("Delete".into())
+                        .background_color(BUTTON_CANCEL_COLOR)
+                        .on_click(move |commands| {
+                            commands.trigger(DeleteProfile(id));
+                            commands.insert_resource(ProfileMenu::Picking);
+                        })
+                        .build(),
+                );
+                commands.entity(row).with_child(
+                    button::Button::builder()
+                        .text("Keep".into())
+                        .on_click(|commands| commands.insert_resource(ProfileMenu::Picking))
+                        .build(),
+                );
+            }
+        }
+
+        commands.entity(panel).add_children(&[heading, row]);
+
+        if *menu == ProfileMenu::Picking {
+            commands.entity(panel).with_child((
+                Node {
+                    column_gap: Val::Px(UI_CONTAINER_GAP),
+                    ..default()
+                },
+                children![
+                    button::Button::builder()
+                        .text("Export save".into())
+                        .on_click(|commands| commands.trigger(ExportSave))
+                        .build(),
+                    button::Button::builder()
+                        .text("Import save".into())
+                        .on_click(|commands| commands.trigger(ImportSave))
+                        .build(),
+                ],
+            ));
+        }
+
+        if !status.is_empty() {
+            commands.entity(panel).with_child((
+                Text(status.0.clone()),
+                TextColor(GHOST_TEXT_COLOR),
+                TextFont {
+                    font_size: 16.,
+                    ..default()
+                },
+            ));
+        }
+    }
+}
+
+fn submit_profile_name(
+    _trigger: Trigger<SubmitProfileName>,
+    mut commands: Commands,
+    profiles: Res<Profiles>,
+    inputs: Query<&TextInput, With<ProfileNameInput>>,
+) {
+    for input in &inputs {
+        commands.trigger(RenameProfile {
+            id: profiles.active().id,
+            name: input.value.clone(),
+        });
+    }
+
+    commands.insert_resource(ProfileMenu::Picking);
+}
+
+fn submit_profile_name_input(
+    trigger: Trigger<TextInputSubmit>,
+    mut commands: Commands,
+    inputs: Query<(), With<ProfileNameInput>>,
+) {
+    if inputs.contains(trigger.event().entity) {
+        commands.trigger(SubmitProfileName);
+    }
+}
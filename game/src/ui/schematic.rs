@@ -0,0 +1,338 @@
+//! A flat top down view of the level drawn with UI nodes. The grid is easier to reason about than
+//! the perspective view, and skipping the 3D level makes it a lighter way to play.
+
+use std::f32::consts::FRAC_PI_2;
+
+use bevy::{ecs::spawn::SpawnIter, prelude::*, window::PrimaryWindow};
+
+use crate::{
+    actions::CWRotation,
+    assets::{IconAssets, TextureAssets},
+    camera::covered_width,
+    game_state::GameState,
+    level::{Level, LevelRoot, Tile, tile_color},
+    player::Player,
+};
+
+use super::*;
+
+pub struct SchematicPlugin;
+
+impl Plugin for SchematicPlugin {
+    fn build(&self, app: &mut App) {
+        app.init_resource::<ViewMode>()
+            .add_systems(
+                Update,
+                (spawn_schematic, place_players, fit_schematic)
+                    .chain()
+                    .run_if(in_state(GameState::InGame)),
+            )
+            .add_systems(Update, (hide_world, update_toggle_button))
+            .add_observer(toggle_view_mode);
+    }
+}
+
+impl SchematicPlugin {
+    pub fn toggle_button() -> impl Bundle {
+        (
+            Name::new("Schematic Button"),
+            SchematicButton,
+            button::Button::builder()
+                .text("Schematic".into())
+                .on_click(|commands| commands.trigger(ToggleViewMode))
+                .build(),
+        )
+    }
+}
+
+#[derive(Debug, Default, Resource, Clone, Copy, PartialEq, Eq)]
+pub enum ViewMode {
+    #[default]
+    Perspective,
+    Schematic,
+}
+
+#[derive(Debug, Event)]
+pub struct ToggleViewMode;
+
+#[derive(Debug, Component)]
+pub struct SchematicButton;
+
+#[derive(Debug, Component)]
+struct SchematicRoot;
+
+/// The grid the tiles and players are laid out on, `min` is the tile in its top left corner.
+#[derive(Debug, Component)]
+struct SchematicGrid {
+    min: IVec2,
+}
+
+#[derive(Debug, Component)]
+struct SchematicPlayer;
+
+/// Pixels per tile.
+const CELL_SIZE: f32 = 48.;
+
+const GLYPH_COLOR: Color = Color::srgba(1., 1., 1., 0.8);
+const PLAYER_COLOR: Color = Color::srgb_u8(0x1a, 0x1c, 0x2c);
+
+fn toggle_view_mode(_trigger: Trigger<ToggleViewMode>, mut view_mode: ResMut<ViewMode>) {
+    *view_mode = match *view_mode {
+        ViewMode::Perspective => ViewMode::Schematic,
+        ViewMode::Schematic => ViewMode::Perspective,
+    };
+}
+
+/// The top left and bottom right tiles of the level's bounding box.
+pub fn bounds(level: &Level) -> (IVec2, IVec2) {
+    level
+        .tiles
+        .keys()
+        .fold((IVec2::MAX, IVec2::MIN), |(min, max), &(x, y)| {
+            let position = IVec2::new(x, y);
+            (min.min(position), max.max(position))
+        })
+}
+
+/// Turns the up arrow icon to point the way a player with `rotation` faces, UI y points down so
+/// positive angles are clockwise.
+fn arrow(rotation: CWRotation) -> Transform {
+    let quarter_turns = match rotation {
+        CWRotation::TwoSeventy => 0.,
+        CWRotation::Zero => 1.,
+        CWRotation::Ninety => 2.,
+        CWRotation::OneEighty => 3.,
+    };
+
+    Transform::from_rotation(Quat::from_rotation_z(quarter_turns * FRAC_PI_2))
+}
+
+/// A `size` pixel square centred on the tile at `position`, with tiles `pitch` pixels apart.
+fn cell(position: IVec2, pitch: f32, size: f32) -> Node {
+    Node {
+        position_type: PositionType::Absolute,
+        left: Val::Px(position.x as f32 * pitch + (pitch - size) / 2.),
+        top: Val::Px(position.y as f32 * pitch + (pitch - size) / 2.),
+        width: Val::Px(size),
+        height: Val::Px(size),
+        ..default()
+    }
+}
+
+fn glyph(image: Handle<Image>, transform: Transform) -> impl Bundle {
+    (
+        Node {
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            ..default()
+        },
+        ImageNode::new(image).with_color(GLYPH_COLOR),
+        transform,
+    )
+}
+
+/// Lays the level's tiles out top down, `pitch` pixels apart, dressing each one with `tile`.
+pub fn tile_grid<B: Bundle>(
+    level: &Level,
+    pitch: f32,
+    mut tile: impl FnMut(Tile) -> B,
+) -> impl Bundle {
+    let (min, max) = bounds(level);
+    let size = (max - min + IVec2::ONE).as_vec2() * pitch;
+
+    (
+        Node {
+            width: Val::Px(size.x),
+            height: Val::Px(size.y),
+            ..default()
+        },
+        Children::spawn(SpawnIter(
+            level
+                .tiles
+                .iter()
+                .map(|(&(x, y), &kind)| {
+                    (cell(IVec2::new(x, y) - min, pitch, pitch * 0.9), tile(kind))
+                })
+                // SpawnIter requires Iterator<_>: 'static
+                .collect::<Vec<_>>()
+                .into_iter(),
+        )),
+    )
+}
+
+fn schematic_tile(tile: Tile, textures: &TextureAssets, icons: &IconAssets) -> impl Bundle {
+    let glyph = match tile {
+        Tile::Start(rotation) => Some(glyph(icons.up.clone(), arrow(rotation))),
+        Tile::CWRot => Some(glyph(textures.cw_rot.clone(), Transform::IDENTITY)),
+        Tile::CCWRot => Some(glyph(textures.ccw_rot.clone(), Transform::IDENTITY)),
+        _ => None,
+    };
+
+    (
+        BackgroundColor(tile_color(tile)),
+        BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS)),
+        Children::spawn(SpawnIter(glyph.into_iter())),
+    )
+}
+
+fn spawn_schematic(
+    mut commands: Commands,
+    view_mode: Res<ViewMode>,
+    level: Res<Level>,
+    textures: Res<TextureAssets>,
+    icons: Res<IconAssets>,
+    roots: Query<Entity, With<SchematicRoot>>,
+) {
+    if !(view_mode.is_changed() || level.is_changed()) {
+        return;
+    }
+
+    for root in &roots {
+        commands.entity(root).despawn();
+    }
+
+    if *view_mode != ViewMode::Schematic || level.tiles.is_empty() {
+        return;
+    }
+
+    commands.spawn((
+        Name::new("Schematic Root"),
+        SchematicRoot,
+        Node {
+            position_type: PositionType::Absolute,
+            width: Val::Percent(100.),
+            height: Val::Percent(100.),
+            justify_content: JustifyContent::Center,
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        // Underneath the rest of the UI
+        GlobalZIndex(-1),
+        children![(
+            Name::new("Schematic Grid"),
+            SchematicGrid {
+                min: bounds(&level).0,
+            },
+            tile_grid(&level, CELL_SIZE, |tile| schematic_tile(
+                tile, &textures, &icons
+            )),
+        )],
+    ));
+}
+
+/// Redraws the player markers whenever a player moves, spawns or goes.
+fn place_players(
+    mut commands: Commands,
+    icons: Res<IconAssets>,
+    players: Query<&Player>,
+    moved: Query<(), Changed<Player>>,
+    mut removed: RemovedComponents<Player>,
+    grids: Query<(Entity, &SchematicGrid)>,
+    new_grids: Query<(), Added<SchematicGrid>>,
+    markers: Query<Entity, With<SchematicPlayer>>,
+) {
+    if moved.is_empty() && removed.read().count() == 0 && new_grids.is_empty() {
+        return;
+    }
+
+    for marker in &markers {
+        commands.entity(marker).despawn();
+    }
+
+    for (grid, SchematicGrid { min }) in &grids {
+        for player in &players {
+            let position = IVec2::new(player.position.0, player.position.1) - *min;
+
+            commands.spawn((
+                Name::new("Schematic Player"),
+                SchematicPlayer,
+                cell(position, CELL_SIZE, CELL_SIZE * 0.6),
+                BackgroundColor(PRIMARY_TEXT_COLOR),
+                BorderRadius::MAX,
+                ChildOf(grid),
+                children![(
+                    Node {
+                        width: Val::Percent(100.),
+                        height: Val::Percent(100.),
+                        ..default()
+                    },
+                    ImageNode::new(icons.up.clone()).with_color(PLAYER_COLOR),
+                    arrow(player.rotation),
+                )],
+            ));
+        }
+    }
+}
+
+/// Centres the grid in the part of the screen the UI columns leave uncovered.
+fn fit_schematic(
+    window: Query<&Window, With<PrimaryWindow>>,
+    ui_root: Query<&Children, With<UiRoot>>,
+    nodes: Query<(&ComputedNode, &GlobalTransform)>,
+    mut roots: Query<&mut Node, With<SchematicRoot>>,
+) -> Result {
+    let padding = Val::Vw(covered_width(window.single()?, &ui_root, &nodes) * 100.);
+
+    for mut root in &mut roots {
+        if root.padding.left != padding {
+            root.padding.left = padding;
+        }
+    }
+
+    Ok(())
+}
+
+/// The schematic stands in for the 3D level and players, so they aren't drawn at all.
+fn hide_world(
+    view_mode: Res<ViewMode>,
+    mut world: Query<&mut Visibility, Or<(With<LevelRoot>, With<Player>)>>,
+) {
+    let visibility = match *view_mode {
+        ViewMode::Perspective => Visibility::Inherited,
+        ViewMode::Schematic => Visibility::Hidden,
+    };
+
+    for mut entity in &mut world {
+        entity.set_if_neq(visibility);
+    }
+}
+
+fn update_toggle_button(
+    view_mode: Res<ViewMode>,
+    mut buttons: Query<&mut button::Button, With<SchematicButton>>,
+) {
+    if !view_mode.is_changed() {
+        return;
+    }
+
+    for mut button in &mut buttons {
+        button.border_color = match *view_mode {
+            ViewMode::Schematic => Some(PRIMARY_TEXT_COLOR),
+            ViewMode::Perspective => None,
+        };
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn arrows_point_the_way_players_face() {
+        // The icon points up, which is -y in UI coordinates
+        let up = Vec3::NEG_Y;
+
+        for (rotation, direction) in [
+            (CWRotation::Zero, Vec3::X),
+            (CWRotation::Ninety, Vec3::Y),
+            (CWRotation::OneEighty, Vec3::NEG_X),
+            (CWRotation::TwoSeventy, Vec3::NEG_Y),
+        ] {
+            let arrow = arrow(rotation) * up;
+            assert!(
+                arrow.distance(direction) < 1e-5,
+                "{rotation:?} points {arrow}"
+            );
+        }
+    }
+}
@@ -1,33 +1,44 @@
 use bevy::{ecs::spawn::SpawnIter, prelude::*, ui::FocusPolicy};
 use challenges::ChallengeState;
+use serde::{Deserialize, Serialize};
 
 use crate::{
     assets::IconAssets,
+    camera::CameraPlugin,
     delayed_command::DelayedCommandExt,
     game_state::{GameState, ResetChallengeState},
-    level::{self, DespawnLevel, LevelCounter, SCENES},
+    level::{self, DespawnLevel, Level, LevelCounter, SCENES},
     music::{MasterVolume, PlayChangeLevelMusic},
     player::DespawnPlayer,
+    progression::Progression,
+    replay::{Replays, WatchReplay},
 };
 
-use super::*;
+use super::{
+    focus::{FocusScope, Focusable},
+    schematic::{self, SchematicPlugin},
+    *,
+};
 
 pub struct SettingsPlugin;
 
 impl Plugin for SettingsPlugin {
     fn build(&self, app: &mut App) {
         app.insert_resource(GameMode::Story)
+            .init_resource::<Progression>()
             .add_systems(OnExit(GameState::MainMenu), spawn_ui)
             .add_systems(Update, update_settings_ui_state)
+            .add_systems(Update, (update_progression_buttons, refresh_level_grid))
             .add_systems(Update, dismiss_settings_ui)
             .add_systems(Update, level_card_interactions)
             .add_observer(create_settings_ui)
             .add_observer(destroy_settings_ui)
+            .add_observer(select_level)
             .add_observer(toggle_volume);
     }
 }
 
-#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq)]
+#[derive(Debug, Resource, Clone, Copy, PartialEq, Eq, Serialize, Deserialize)]
 pub enum GameMode {
     Story,
     Challenge,
@@ -36,6 +47,7 @@ pub enum GameMode {
 fn spawn_ui(mut commands: Commands, icons: Res<IconAssets>) {
     commands.spawn((
         Name::new("Settings UI Container"),
+        FocusScope::Base,
         Node {
             width: Val::Percent(100.),
             height: Val::Percent(100.),
@@ -45,12 +57,21 @@ fn spawn_ui(mut commands: Commands, icons: Res<IconAssets>) {
             padding: UiRect::all(Val::Px(UI_CONTAINER_PADDING)),
             ..default()
         },
-        children![
-            button::Button::builder()
-                .on_click(|commands| commands.trigger(CreateSettingsUI))
-                .icon(icons.bars.clone())
-                .build()
-        ],
+        children![(
+            Node {
+                column_gap: Val::Px(UI_CONTAINER_GAP),
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            children![
+                CameraPlugin::reset_view_button(),
+                SchematicPlugin::toggle_button(),
+                button::Button::builder()
+                    .on_click(|commands| commands.trigger(CreateSettingsUI))
+                    .icon(icons.bars.clone())
+                    .build()
+            ],
+        )],
     ));
 }
 
@@ -66,15 +87,24 @@ pub struct SettingsUIRoot;
 #[derive(Debug, Component, Clone, Copy)]
 pub struct LevelCard(usize);
 
+#[derive(Debug, Component)]
+pub struct LevelGrid;
+
 const INCOMPLETE_COLOR: Color = Color::srgb_u8(0x41, 0x53, 0x69);
 const SUCCESS_COLOR: Color = Color::srgba_u8(0x0c, 0xc4, 0x0f, 0xdd);
+/// The longest side of a level thumbnail, in pixels.
+const THUMBNAIL_SIZE: f32 = 72.;
+/// Keeps the tiles of small levels from being blown up.
+const THUMBNAIL_MAX_PITCH: f32 = 12.;
 
 fn create_settings_ui(
     _trigger: Trigger<CreateSettingsUI>,
     mut commands: Commands,
     game_mode: Res<GameMode>,
+    progression: Res<Progression>,
     icons: Res<IconAssets>,
     challenges: ResMut<ChallengeState>,
+    replays: Res<Replays>,
     level_counter: Res<LevelCounter>,
     master_volume: Res<MasterVolume>,
     mut game_state: ResMut<NextState<GameState>>,
@@ -95,11 +125,14 @@ fn create_settings_ui(
         },
         BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
         Interaction::default(),
+        FocusScope::Modal,
         children![settings_panel(
             *game_mode,
+            *progression,
             &*master_volume,
             &*icons,
             &*challenges,
+            &*replays,
             **level_counter
         )],
     ));
@@ -107,9 +140,11 @@ fn create_settings_ui(
 
 fn settings_panel(
     game_mode: GameMode,
+    progression: Progression,
     master_volume: &MasterVolume,
     icons: &IconAssets,
     challenges: &ChallengeState,
+    replays: &Replays,
     level_counter: usize,
 ) -> impl Bundle {
     (
@@ -128,8 +163,9 @@ fn settings_panel(
         children![
             header(game_mode, master_volume, icons),
             game_mode_explanation(game_mode),
+            progression_controls(progression),
             horizontal_line(),
-            level_grid(challenges, level_counter),
+            level_grid(progression, icons, challenges, replays, level_counter),
         ],
     )
 }
@@ -257,9 +293,61 @@ fn game_mode_explanation(game_mode: GameMode) -> impl Bundle {
     )
 }
 
-fn level_grid(challenges: &ChallengeState, level_counter: usize) -> impl Bundle {
+#[derive(Debug, Component)]
+pub struct ProgressionButton(Progression);
+
+fn progression_controls(progression: Progression) -> impl Bundle {
+    (
+        Name::new("Progression Controls"),
+        Node {
+            column_gap: Val::Px(UI_CONTAINER_GAP),
+            align_items: AlignItems::Center,
+            ..default()
+        },
+        Children::spawn((
+            Spawn((Text("Unlock levels:".into()), TextColor(PRIMARY_TEXT_COLOR))),
+            SpawnIter(
+                [
+                    Progression::Open,
+                    Progression::Sequential,
+                    Progression::Chapters,
+                ]
+                .into_iter()
+                .map(move |option| {
+                    let mut button = button::Button::builder()
+                        .on_click(move |commands| commands.insert_resource(option))
+                        .text(
+                            match option {
+                                Progression::Open => "All at once",
+                                Progression::Sequential => "One by one",
+                                Progression::Chapters => "By chapter",
+                            }
+                            .into(),
+                        );
+
+                    if option == progression {
+                        button = button.border_color(PRIMARY_TEXT_COLOR);
+                    }
+
+                    (ProgressionButton(option), button.build())
+                }),
+            ),
+        )),
+    )
+}
+
+fn level_grid(
+    progression: Progression,
+    icons: &IconAssets,
+    challenges: &ChallengeState,
+    replays: &Replays,
+    level_counter: usize,
+) -> impl Bundle {
+    let unlocked = progression.unlocked(challenges);
+
     (
         Name::new("Level Grid"),
+        LevelGrid,
         Node {
             display: Display::Grid,
             grid_template_columns: vec![RepeatedGridTrack::fr(6, 1.)],
@@ -277,9 +365,26 @@ fn level_grid(challenges: &ChallengeState, level_counter: usize) -> impl Bundle
                     _ => None,
                 })
                 .map(|(index, level)| {
-                    let challenge = challenges.get(level.name).copied().unwrap_or_default();
-
-                    level_card(index, index == level_counter, level.name, challenge)
+                    let challenge = challenges.get(level.id).cloned().unwrap_or_default();
+                    let trackers = level
+                        .challenges
+                        .iter()
+                        .map(|level_challenge| challenge.is_completed(&**level_challenge))
+                        .collect();
+                    let has_replay = replays.contains_key(level.id);
+                    // The level being played stays open, however it was reached
+                    let lock =
+                        (!unlocked[index] && index != level_counter).then(|| icons.lock.clone());
+
+                    level_card(
+                        index,
+                        index == level_counter,
+                        level,
+                        challenge,
+                        trackers,
+                        has_replay,
+                        lock,
+                    )
                 })
                 // Need to allocate an intermediate vector to avoid borrowing &ChallengeState
                 // SpawnIter requires Iterator<_>: 'static
@@ -292,12 +397,18 @@ fn level_grid(challenges: &ChallengeState, level_counter: usize) -> impl Bundle
 fn level_card(
     index: usize,
     selected: bool,
-    name: &str,
+    level: &Level,
     challenge: challenges::ChallengeRecord,
+    trackers: Vec<bool>,
+    has_replay: bool,
+    lock: Option<Handle<Image>>,
 ) -> impl Bundle {
+    let thumbnail = level_thumbnail(level, lock.is_some() || !challenge.level_completed);
+
     (
         Name::new(format!("Level Card {}", index)),
         LevelCard(index),
+        Focusable,
         Interaction::default(),
         Node {
             justify_content: JustifyContent::FlexStart,
@@ -317,20 +428,75 @@ fn level_card(
             false => INCOMPLETE_COLOR,
         }),
         BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS * 2.0)),
-        children![
-            Text(name.into()),
-            (
+        Children::spawn((
+            Spawn(Text(level.name.into())),
+            Spawn(thumbnail),
+            SpawnIter(lock.map(lock_icon).into_iter()),
+            Spawn((
                 Node {
                     column_gap: Val::Px(UI_CONTAINER_GAP),
                     ..default()
                 },
-                Children::spawn(SpawnIter(
-                    vec![challenge.steps, challenge.commands, challenge.waste,]
-                        .into_iter()
-                        .filter_map(|completed| { Some(challenge_tracker(completed?)) }),
-                )),
-            )
-        ],
+                Children::spawn(SpawnIter(trackers.into_iter().map(challenge_tracker))),
+            )),
+            SpawnIter(
+                challenge
+                    .best_summary()
+                    .map(|best| {
+                        (
+                            Text(best),
+                            TextFont {
+                                font_size: 14.,
+                                ..default()
+                            },
+                        )
+                    })
+                    .into_iter(),
+            ),
+            SpawnIter(has_replay.then(|| replay_button(index)).into_iter()),
+        )),
+    )
+}
+
+fn lock_icon(lock: Handle<Image>) -> impl Bundle {
+    (
+        Name::new("Lock Icon"),
+        Node {
+            width: Val::Px(21.),
+            height: Val::Px(24.),
+            ..default()
+        },
+        ImageNode::new(lock),
+    )
+}
+
+/// A pictogram of the level's tiles, drawn in grey until it's been completed.
+fn level_thumbnail(level: &Level, greyed: bool) -> impl Bundle {
+    let (min, max) = schematic::bounds(level);
+    let extent = (max - min + IVec2::ONE).max_element();
+    let pitch = (THUMBNAIL_SIZE / extent as f32).min(THUMBNAIL_MAX_PITCH);
+
+    (
+        Name::new("Level Thumbnail"),
+        schematic::tile_grid(level, pitch, move |tile| {
+            let color = level::tile_color(tile);
+
+            BackgroundColor(match greyed {
+                true => Color::from(Hsla::from(color).with_saturation(0.)).with_alpha(0.6),
+                false => color,
+            })
+        }),
+    )
+}
+
+fn replay_button(index: usize) -> impl Bundle {
+    (
+        button::Button::builder()
+            .text("Watch replay".into())
+            .on_click(move |commands| commands.trigger(WatchReplay(index)))
+            .build(),
+        // Stop the click from also selecting the level underneath
+        FocusPolicy::Block,
     )
 }
 
@@ -389,6 +555,42 @@ fn update_settings_ui_state(
     }
 }
 
+fn update_progression_buttons(
+    progression: Res<Progression>,
+    mut buttons: Query<(&ProgressionButton, &mut button::Button)>,
+) {
+    if !progression.is_changed() {
+        return;
+    }
+
+    for (ProgressionButton(option), mut button) in &mut buttons {
+        button.border_color = (*option == *progression).then_some(PRIMARY_TEXT_COLOR);
+    }
+}
+
+/// Locks are drawn on the level cards, so the grid is rebuilt when the rules change.
+fn refresh_level_grid(
+    mut commands: Commands,
+    progression: Res<Progression>,
+    icons: Res<IconAssets>,
+    challenges: Res<ChallengeState>,
+    replays: Res<Replays>,
+    level_counter: Res<LevelCounter>,
+    grids: Query<(Entity, &ChildOf), With<LevelGrid>>,
+) {
+    if !progression.is_changed() {
+        return;
+    }
+
+    for (grid, child_of) in &grids {
+        commands.entity(grid).despawn();
+        commands.spawn((
+            level_grid(*progression, &icons, &challenges, &replays, **level_counter),
+            ChildOf(child_of.parent()),
+        ));
+    }
+}
+
 fn destroy_settings_ui(
     _trigger: Trigger<DestroySettingsUI>,
     roots: Query<Entity, With<SettingsUIRoot>>,
@@ -416,7 +618,6 @@ fn level_card_interactions(
     mut cards: Query<(&LevelCard, &Interaction, &mut BorderColor), Changed<Interaction>>,
     level_counter: Res<LevelCounter>,
     mut commands: Commands,
-    mut game_state: ResMut<NextState<GameState>>,
 ) {
     for (level, interaction, mut border_color) in &mut cards {
         let level_id = level.0;
@@ -427,25 +628,59 @@ fn level_card_interactions(
         }
 
         match interaction {
-            Interaction::Pressed => match SCENES.get(level_id) {
-                Some(level::Scene::Level(_)) => {
-                    commands.trigger(DestroySettingsUI);
-                    commands.trigger(DespawnLevel);
-                    commands.trigger(DespawnPlayer);
-                    commands.trigger(PlayChangeLevelMusic);
-                    game_state.set(GameState::InGame);
-                    commands.delayed(2., move |commands| {
-                        commands.trigger(level::LoadLevel(level_id))
-                    });
-                }
-                other => tracing::warn!(?other, "attempted to select non-level scene"),
-            },
+            Interaction::Pressed => commands.trigger(SelectLevel(level_id)),
             Interaction::Hovered => *border_color = BorderColor(PRIMARY_TEXT_COLOR),
             Interaction::None => *border_color = BorderColor::default(),
         }
     }
 }
 
+/// Returns the scene index of the `n`th card (zero based) in the level grid.
+pub fn level_grid_scene(n: usize) -> Option<usize> {
+    SCENES
+        .iter()
+        .enumerate()
+        .filter(|(_, scene)| matches!(scene, level::Scene::Level(_)))
+        .nth(n)
+        .map(|(index, _)| index)
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct SelectLevel(pub usize);
+
+fn select_level(
+    trigger: Trigger<SelectLevel>,
+    level_counter: Res<LevelCounter>,
+    progression: Res<Progression>,
+    challenges: Res<ChallengeState>,
+    mut commands: Commands,
+    mut game_state: ResMut<NextState<GameState>>,
+) {
+    let level_id = trigger.event().0;
+
+    if level_id == **level_counter {
+        return;
+    }
+
+    if progression.unlocked(&challenges).get(level_id) == Some(&false) {
+        return;
+    }
+
+    match SCENES.get(level_id) {
+        Some(level::Scene::Level(_)) => {
+            commands.trigger(DestroySettingsUI);
+            commands.trigger(DespawnLevel);
+            commands.trigger(DespawnPlayer);
+            commands.trigger(PlayChangeLevelMusic);
+            game_state.set(GameState::InGame);
+            commands.delayed(2., move |commands| {
+                commands.trigger(level::LoadLevel(level_id))
+            });
+        }
+        other => tracing::warn!(?other, "attempted to select non-level scene"),
+    }
+}
+
 #[derive(Debug, Component)]
 pub struct VolumeButton;
 
@@ -0,0 +1,371 @@
+use bevy::{prelude::*, ui::FocusPolicy};
+
+use crate::{
+    actions::{ActionPlan, LoadActionPlan},
+    clipboard,
+    level::{Level, LoadNextLevel},
+    replay::ReplayMode,
+    share_code::ShareCode,
+    simulation::SimulationState,
+};
+
+use super::{
+    focus::FocusScope,
+    text_input::{TextInput, TextInputFocus, TextInputSubmit, edit_text_input, text_input},
+    *,
+};
+
+pub struct SharePlugin;
+
+impl Plugin for SharePlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(ShareStatus::default())
+            .add_systems(Update, update_share_status)
+            .add_systems(Update, dismiss_share_panel.after(edit_text_input))
+            .add_observer(open_share_panel)
+            .add_observer(close_share_panel)
+            .add_observer(copy_share_code)
+            .add_observer(paste_share_code)
+            .add_observer(submit_share_panel)
+            .add_observer(submit_share_input)
+            .add_observer(apply_share_code)
+            .add_observer(close_on_level_change);
+    }
+}
+
+/// Opens the share panel, showing `code` or the current plan's share code if there isn't one.
+#[derive(Debug, Clone, Default, Event)]
+pub struct OpenSharePanel {
+    pub code: Option<String>,
+}
+
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CloseSharePanel;
+
+/// Copies the share code for the current plan, falling back to showing it in the share panel.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct CopyShareCode;
+
+/// Loads a share code from the clipboard, falling back to asking for it in the share panel.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct PasteShareCode;
+
+/// Loads the code typed into the share panel.
+#[derive(Debug, Clone, Copy, Event)]
+pub struct SubmitSharePanel;
+
+#[derive(Debug, Clone, Event)]
+pub struct ApplyShareCode(pub String);
+
+/// The message shown under the share panel's input.
+#[derive(Debug, Clone, Default, Resource, Deref, DerefMut)]
+pub struct ShareStatus(pub String);
+
+#[derive(Debug, Component)]
+pub struct SharePanelRoot;
+
+#[derive(Debug, Component)]
+pub struct ShareCodeInput;
+
+#[derive(Debug, Component)]
+pub struct ShareStatusText;
+
+impl SharePlugin {
+    pub fn share_button() -> impl Bundle {
+        button::Button::builder()
+            .text("Share".into())
+            .on_click(|commands| commands.trigger(OpenSharePanel::default()))
+            .build()
+    }
+}
+
+fn open_share_panel(
+    trigger: Trigger<OpenSharePanel>,
+    mut commands: Commands,
+    level: Res<Level>,
+    action_plan: Res<ActionPlan>,
+    mut focus: ResMut<TextInputFocus>,
+    existing: Query<(), With<SharePanelRoot>>,
+) {
+    if !existing.is_empty() {
+        return;
+    }
+
+    let code = trigger
+        .event()
+        .code
+        .clone()
+        .unwrap_or_else(|| ShareCode::new(&level, &action_plan).to_string());
+
+    let input = commands
+        .spawn((ShareCodeInput, text_input(TextInput::new(code))))
+        .id();
+
+    let mut buttons = vec![];
+
+    if clipboard::AVAILABLE {
+        buttons.push(
+            commands
+                .spawn(
+                    button::Button::builder()
+                        .text("Copy".into())
+                        .on_click(|commands| commands.trigger(CopyShareCode))
+                        .build(),
+                )
+                .id(),
+        );
+        buttons.push(
+            commands
+                .spawn(
+                    button::Button::builder()
+                        .text("Paste".into())
+                        .on_click(|commands| commands.trigger(PasteShareCode))
+                        .build(),
+                )
+                .id(),
+        );
+    }
+
+    buttons.push(
+        commands
+            .spawn(
+                button::Button::builder()
+                    .text("Load".into())
+                    .background_color(BUTTON_SUCCESS_COLOR)
+                    .on_click(|commands| commands.trigger(SubmitSharePanel))
+                    .build(),
+            )
+            .id(),
+    );
+    buttons.push(
+        commands
+            .spawn(
+                button::Button::builder()
+                    .text("Close".into())
+                    .background_color(BUTTON_CANCEL_COLOR)
+                    .on_click(|commands| commands.trigger(CloseSharePanel))
+                    .build(),
+            )
+            .id(),
+    );
+
+    let button_row = commands
+        .spawn(Node {
+            column_gap: Val::Px(UI_CONTAINER_GAP),
+            ..default()
+        })
+        .add_children(&buttons)
+        .id();
+
+    let panel = commands
+        .spawn((
+            Name::new("Share Panel"),
+            Node {
+                flex_direction: FlexDirection::Column,
+                align_items: AlignItems::Start,
+                padding: UiRect::all(Val::Px(UI_CONTAINER_PADDING)),
+                row_gap: Val::Px(UI_CONTAINER_GAP),
+                ..default()
+            },
+            BorderRadius::all(Val::Px(UI_CONTAINER_RADIUS)),
+            BackgroundColor(Color::srgba_u8(0x56, 0x6c, 0x86, 0xff)),
+            FocusPolicy::Block,
+            children![
+                (
+                    Text("Share Code".into()),
+                    TextColor(PRIMARY_TEXT_COLOR),
+                    TextFont {
+                        font_size: 45.,
+                        ..default()
+                    },
+                ),
+                (
+                    Text(
+                        match clipboard::AVAILABLE {
+                            true => "Copy this code to share your solution, or paste one to load it",
+                            false => "Write this code down to share your solution, or type one in to load it",
+                        }
+                        .into()
+                    ),
+                    TextColor(PRIMARY_TEXT_COLOR),
+                    TextFont {
+                        font_size: 16.,
+                        ..default()
+                    },
+                ),
+                horizontal_line(),
+            ],
+        ))
+        .add_child(input)
+        .with_child((
+            ShareStatusText,
+            Text::default(),
+            TextColor(GHOST_TEXT_COLOR),
+            TextFont {
+                font_size: 16.,
+                ..default()
+            },
+        ))
+        .add_child(button_row)
+        .id();
+
+    commands
+        .spawn((
+            Name::new("Share Panel Root"),
+            SharePanelRoot,
+            Node {
+                width: Val::Percent(100.),
+                height: Val::Percent(100.),
+                position_type: PositionType::Absolute,
+                justify_content: JustifyContent::Center,
+                align_items: AlignItems::Center,
+                ..default()
+            },
+            BackgroundColor(Color::srgba(0.1, 0.1, 0.1, 0.6)),
+            GlobalZIndex(1),
+            Interaction::default(),
+            FocusScope::Modal,
+        ))
+        .add_child(panel);
+
+    **focus = Some(input);
+}
+
+fn close_share_panel(
+    _trigger: Trigger<CloseSharePanel>,
+    mut commands: Commands,
+    mut status: ResMut<ShareStatus>,
+    roots: Query<Entity, With<SharePanelRoot>>,
+) {
+    for root in &roots {
+        commands.entity(root).despawn();
+    }
+
+    status.clear();
+}
+
+fn dismiss_share_panel(
+    mut commands: Commands,
+    keys: Res<ButtonInput<KeyCode>>,
+    roots: Query<Ref<Interaction>, With<SharePanelRoot>>,
+) {
+    for interaction in &roots {
+        let clicked = interaction.is_changed() && *interaction == Interaction::Pressed;
+
+        // A focused text input takes the first Escape itself
+        if clicked || keys.just_pressed(KeyCode::Escape) {
+            commands.trigger(CloseSharePanel);
+        }
+    }
+}
+
+fn close_on_level_change(_trigger: Trigger<LoadNextLevel>, mut commands: Commands) {
+    commands.trigger(CloseSharePanel);
+}
+
+fn copy_share_code(
+    _trigger: Trigger<CopyShareCode>,
+    mut commands: Commands,
+    level: Res<Level>,
+    action_plan: Res<ActionPlan>,
+    mut status: ResMut<ShareStatus>,
+) {
+    let code = ShareCode::new(&level, &action_plan).to_string();
+
+    if clipboard::write(&code) {
+        **status = format!("Copied {code}");
+    } else {
+        commands.trigger(OpenSharePanel::default());
+    }
+}
+
+fn paste_share_code(
+    _trigger: Trigger<PasteShareCode>,
+    mut commands: Commands,
+    mut status: ResMut<ShareStatus>,
+) {
+    match clipboard::read() {
+        Some(code) => commands.trigger(ApplyShareCode(code)),
+        None => {
+            commands.trigger(OpenSharePanel::default());
+            **status = "Nothing to paste, type the code in instead".into();
+        }
+    }
+}
+
+fn submit_share_panel(
+    _trigger: Trigger<SubmitSharePanel>,
+    mut commands: Commands,
+    inputs: Query<&TextInput, With<ShareCodeInput>>,
+) {
+    for input in &inputs {
+        commands.trigger(ApplyShareCode(input.value.clone()));
+    }
+}
+
+fn submit_share_input(
+    trigger: Trigger<TextInputSubmit>,
+    mut commands: Commands,
+    inputs: Query<(), With<ShareCodeInput>>,
+) {
+    if inputs.contains(trigger.event().entity) {
+        commands.trigger(ApplyShareCode(trigger.event().value.clone()));
+    }
+}
+
+fn apply_share_code(
+    trigger: Trigger<ApplyShareCode>,
+    mut commands: Commands,
+    level: Res<Level>,
+    game_state: Res<State<GameState>>,
+    simulation_state: Res<State<SimulationState>>,
+    replay: Res<ReplayMode>,
+    mut status: ResMut<ShareStatus>,
+    mut inputs: Query<&mut TextInput, With<ShareCodeInput>>,
+) {
+    let text = &trigger.event().0;
+
+    if *game_state != GameState::InGame
+        || *simulation_state != SimulationState::Stopped
+        || replay.is_active()
+    {
+        return;
+    }
+
+    let code = ShareCode::parse(text).and_then(|code| code.validate(&level).map(|_| code));
+
+    match code {
+        Ok(code) => {
+            commands.trigger(LoadActionPlan(code.plan));
+            commands.trigger(CloseSharePanel);
+        }
+        Err(error) => {
+            // Show what was rejected, e.g. when it came from the clipboard
+            if inputs.is_empty() {
+                commands.trigger(OpenSharePanel {
+                    code: Some(text.clone()),
+                });
+            }
+
+            for mut input in &mut inputs {
+                input.value.clone_from(text);
+            }
+
+            **status = error.to_string();
+        }
+    }
+}
+
+fn update_share_status(
+    status: Res<ShareStatus>,
+    mut texts: Query<&mut Text, With<ShareStatusText>>,
+    added: Query<(), Added<ShareStatusText>>,
+) {
+    if !status.is_changed() && added.is_empty() {
+        return;
+    }
+
+    for mut text in &mut texts {
+        text.0.clone_from(&status);
+    }
+}
@@ -0,0 +1,173 @@
+use bevy::{
+    input::{
+        ButtonState,
+        keyboard::{Key, KeyboardInput},
+    },
+    prelude::*,
+};
+
+use super::constants::*;
+
+pub struct TextInputPlugin;
+
+impl Plugin for TextInputPlugin {
+    fn build(&self, app: &mut App) {
+        app.insert_resource(TextInputFocus::default())
+            .add_systems(Update, focus_text_input)
+            .add_systems(Update, edit_text_input.after(focus_text_input))
+            .add_systems(Update, update_text_input.after(edit_text_input));
+    }
+}
+
+/// A single line text field, typing goes to whichever input holds the [`TextInputFocus`].
+#[derive(Debug, Clone, Component)]
+#[require(Node, Interaction)]
+pub struct TextInput {
+    pub value: String,
+    pub max_length: usize,
+}
+
+impl TextInput {
+    pub fn new(value: impl Into<String>) -> Self {
+        TextInput {
+            value: value.into(),
+            max_length: 64,
+        }
+    }
+}
+
+/// The text input receiving keyboard input, while this is set other keyboard shortcuts are ignored.
+#[derive(Debug, Clone, Copy, Default, Resource, Deref, DerefMut)]
+pub struct TextInputFocus(pub Option<Entity>);
+
+/// Fired when Enter is pressed in a focused text input.
+#[derive(Debug, Clone, Event)]
+pub struct TextInputSubmit {
+    pub entity: Entity,
+    pub value: String,
+}
+
+#[derive(Debug, Component)]
+struct TextInputDisplay;
+
+pub fn text_input(input: TextInput) -> impl Bundle {
+    (
+        input,
+        Node {
+            min_width: Val::Px(300.),
+            padding: UiRect::all(Val::Px(8.)),
+            border: UiRect::all(Val::Px(BUTTON_BORDER_THICKNESS)),
+            ..default()
+        },
+        BorderRadius::all(Val::Px(BUTTON_BORDER_RADIUS)),
+        BorderColor(GHOST_TEXT_COLOR),
+        BackgroundColor(UI_BACKGROUND_COLOR),
+        children![(
+            TextInputDisplay,
+            Text::default(),
+            TextColor(PRIMARY_TEXT_COLOR)
+        )],
+    )
+}
+
+fn focus_text_input(
+    mut focus: ResMut<TextInputFocus>,
+    inputs: Query<(Entity, &Interaction), (With<TextInput>, Changed<Interaction>)>,
+    mut removed: RemovedComponents<TextInput>,
+    mouse: Res<ButtonInput<MouseButton>>,
+) {
+    for entity in removed.read() {
+        if **focus == Some(entity) {
+            **focus = None;
+        }
+    }
+
+    for (entity, interaction) in &inputs {
+        if *interaction == Interaction::Pressed {
+            **focus = Some(entity);
+            return;
+        }
+    }
+
+    // Clicking anywhere else gives up the focus, unless it was handed over by that same click
+    if mouse.just_pressed(MouseButton::Left) && focus.is_some() && !focus.is_changed() {
+        **focus = None;
+    }
+}
+
+/// Runs before anything else reading the keyboard, and swallows the keys it types with so they
+/// don't also trigger shortcuts.
+pub fn edit_text_input(
+    mut commands: Commands,
+    mut focus: ResMut<TextInputFocus>,
+    mut keyboard: EventReader<KeyboardInput>,
+    mut keys: ResMut<ButtonInput<KeyCode>>,
+    mut inputs: Query<&mut TextInput>,
+) {
+    let Some(entity) = **focus else {
+        keyboard.clear();
+        return;
+    };
+
+    let Ok(mut input) = inputs.get_mut(entity) else {
+        return;
+    };
+
+    keys.clear();
+
+    for event in keyboard.read() {
+        if event.state != ButtonState::Pressed {
+            continue;
+        }
+
+        match &event.logical_key {
+            Key::Backspace => {
+                input.value.pop();
+            }
+            Key::Enter => commands.trigger(TextInputSubmit {
+                entity,
+                value: input.value.clone(),
+            }),
+            Key::Escape => **focus = None,
+            _ => {
+                let Some(text) = &event.text else {
+                    continue;
+                };
+
+                for c in text.chars().filter(|c| !c.is_control()) {
+                    if input.value.chars().count() < input.max_length {
+                        input.value.push(c);
+                    }
+                }
+            }
+        }
+    }
+}
+
+fn update_text_input(
+    focus: Res<TextInputFocus>,
+    mut inputs: Query<(Entity, Ref<TextInput>, &Children, &mut BorderColor)>,
+    mut display: Query<&mut Text, With<TextInputDisplay>>,
+) {
+    for (entity, input, children, mut border_color) in &mut inputs {
+        if !(input.is_changed() || focus.is_changed()) {
+            continue;
+        }
+
+        let focused = **focus == Some(entity);
+
+        *border_color = BorderColor(match focused {
+            true => PRIMARY_TEXT_COLOR,
+            false => GHOST_TEXT_COLOR,
+        });
+
+        for child in children {
+            if let Ok(mut text) = display.get_mut(*child) {
+                **text = match focused {
+                    true => format!("{}_", input.value),
+                    false => input.value.clone(),
+                };
+            }
+        }
+    }
+}
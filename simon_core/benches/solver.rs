@@ -0,0 +1,29 @@
+use criterion::{Criterion, black_box, criterion_group, criterion_main};
+use simon_core::{
+    level::{Level, SCENES, Scene},
+    solver::enumerate_solutions,
+};
+
+fn level_from_name(name: &str) -> &'static Level {
+    SCENES
+        .iter()
+        .find_map(|scene| match scene {
+            Scene::Level(level) if level.name == name => Some(level),
+            _ => None,
+        })
+        .unwrap()
+}
+
+fn solver(c: &mut Criterion) {
+    let mut group = c.benchmark_group("enumerate_solutions");
+
+    for name in ["Blizzard", "Crucible", "Transcendence"] {
+        let level = level_from_name(name);
+        group.bench_function(name, |b| b.iter(|| enumerate_solutions(black_box(level))));
+    }
+
+    group.finish();
+}
+
+criterion_group!(benches, solver);
+criterion_main!(benches);
@@ -0,0 +1,300 @@
+use std::ops::{Deref, DerefMut};
+
+use serde::{Deserialize, Serialize};
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, PartialOrd, Ord, Serialize, Deserialize)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Event))]
+pub enum Action {
+    Forward,
+    Right,
+    Backward,
+    Left,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, Default)]
+pub enum CWRotation {
+    #[default]
+    Zero,
+    Ninety,
+    OneEighty,
+    TwoSeventy,
+}
+
+impl CWRotation {
+    pub fn rotate_cw(self) -> Self {
+        match self {
+            CWRotation::Zero => CWRotation::Ninety,
+            CWRotation::Ninety => CWRotation::OneEighty,
+            CWRotation::OneEighty => CWRotation::TwoSeventy,
+            CWRotation::TwoSeventy => CWRotation::Zero,
+        }
+    }
+
+    pub fn rotate_ccw(self) -> Self {
+        match self {
+            CWRotation::Zero => CWRotation::TwoSeventy,
+            CWRotation::Ninety => CWRotation::Zero,
+            CWRotation::OneEighty => CWRotation::Ninety,
+            CWRotation::TwoSeventy => CWRotation::OneEighty,
+        }
+    }
+}
+
+impl CWRotation {
+    pub fn to_combinator(self) -> fn(&Action) -> Action {
+        match self {
+            CWRotation::Zero => |action| *action,
+            CWRotation::Ninety => Action::rotate_cw,
+            CWRotation::OneEighty => Action::rotate_180,
+            CWRotation::TwoSeventy => Action::rotate_ccw,
+        }
+    }
+}
+
+impl std::fmt::Display for Action {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Action::Forward => write!(f, "↑"),
+            Action::Right => write!(f, "→"),
+            Action::Backward => write!(f, "↓"),
+            Action::Left => write!(f, "←"),
+        }
+    }
+}
+
+impl Action {
+    pub fn rotate_cw(&self) -> Self {
+        match self {
+            Action::Forward => Action::Right,
+            Action::Right => Action::Backward,
+            Action::Backward => Action::Left,
+            Action::Left => Action::Forward,
+        }
+    }
+
+    pub fn rotate_ccw(&self) -> Self {
+        match self {
+            Action::Forward => Action::Left,
+            Action::Right => Action::Forward,
+            Action::Backward => Action::Right,
+            Action::Left => Action::Backward,
+        }
+    }
+
+    pub fn rotate_180(&self) -> Self {
+        match self {
+            Action::Forward => Action::Backward,
+            Action::Right => Action::Left,
+            Action::Backward => Action::Forward,
+            Action::Left => Action::Right,
+        }
+    }
+
+    pub fn cw_rotation(&self, target: Action) -> CWRotation {
+        match (self, target) {
+            (Action::Forward, Action::Forward) => CWRotation::Zero,
+            (Action::Forward, Action::Right) => CWRotation::Ninety,
+            (Action::Forward, Action::Backward) => CWRotation::OneEighty,
+            (Action::Forward, Action::Left) => CWRotation::TwoSeventy,
+            (Action::Right, Action::Forward) => CWRotation::TwoSeventy,
+            (Action::Right, Action::Right) => CWRotation::Zero,
+            (Action::Right, Action::Backward) => CWRotation::Ninety,
+            (Action::Right, Action::Left) => CWRotation::OneEighty,
+            (Action::Backward, Action::Forward) => CWRotation::OneEighty,
+            (Action::Backward, Action::Right) => CWRotation::TwoSeventy,
+            (Action::Backward, Action::Backward) => CWRotation::Zero,
+            (Action::Backward, Action::Left) => CWRotation::Ninety,
+            (Action::Left, Action::Forward) => CWRotation::Ninety,
+            (Action::Left, Action::Right) => CWRotation::OneEighty,
+            (Action::Left, Action::Backward) => CWRotation::TwoSeventy,
+            (Action::Left, Action::Left) => CWRotation::Zero,
+        }
+    }
+}
+
+impl Action {
+    /// The single character used for this action in share codes.
+    pub fn code(self) -> char {
+        match self {
+            Action::Forward => 'F',
+            Action::Right => 'R',
+            Action::Backward => 'B',
+            Action::Left => 'L',
+        }
+    }
+
+    pub fn from_code(code: char) -> Option<Self> {
+        match code.to_ascii_uppercase() {
+            'F' => Some(Action::Forward),
+            'R' => Some(Action::Right),
+            'B' => Some(Action::Backward),
+            'L' => Some(Action::Left),
+            _ => None,
+        }
+    }
+}
+
+impl From<Action> for String {
+    fn from(value: Action) -> Self {
+        match value {
+            Action::Forward => "Forward".into(),
+            Action::Backward => "Backward".into(),
+            Action::Left => "Left".into(),
+            Action::Right => "Right".into(),
+        }
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct ActionPlan(pub Vec<Action>);
+
+impl Deref for ActionPlan {
+    type Target = Vec<Action>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ActionPlan {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl ActionPlan {
+    pub fn phase_iter(&self) -> impl Iterator<Item = ActionPlan> {
+        (1..=self.len()).map(|i| {
+            let mut phase = self.clone();
+            phase.rotate_right(i);
+            phase
+        })
+    }
+
+    pub fn canonicalize_phase(&self) -> Self {
+        self.phase_iter()
+            .map(|plan| plan.canonicalize_rotation().canonicalize_mirror())
+            .min()
+            .unwrap_or_default()
+    }
+
+    pub fn mirror(&self) -> Self {
+        ActionPlan(
+            self.iter()
+                .map(|action| match action {
+                    action @ (Action::Forward | Action::Backward) => *action,
+                    Action::Left => Action::Right,
+                    Action::Right => Action::Left,
+                })
+                .collect(),
+        )
+    }
+
+    pub fn canonicalize_mirror(&self) -> Self {
+        let mirror = self.mirror();
+
+        if self < &mirror { self.clone() } else { mirror }
+    }
+
+    pub fn canonicalize_rotation(&self) -> Self {
+        if self.is_empty() {
+            return Self::default();
+        }
+
+        let rotate = self[0].cw_rotation(Action::Forward).to_combinator();
+
+        Self(self.iter().map(rotate).collect())
+    }
+
+    pub fn canonicalize(&self) -> Self {
+        self.canonicalize_rotation()
+            .canonicalize_mirror()
+            .canonicalize_phase()
+    }
+
+    /// Encodes the plan as one character per action, e.g. `FRRBL`.
+    pub fn to_code(&self) -> String {
+        self.iter().map(|action| action.code()).collect()
+    }
+
+    /// Decodes a plan produced by [`ActionPlan::to_code`], returning the first invalid character on
+    /// failure. Whitespace is ignored and the code is case insensitive.
+    pub fn from_code(code: &str) -> Result<Self, char> {
+        code.chars()
+            .filter(|c| !c.is_whitespace())
+            .map(|c| Action::from_code(c).ok_or(c))
+            .collect::<Result<_, _>>()
+            .map(ActionPlan)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use proptest::prelude::*;
+
+    use super::*;
+
+    use Action::*;
+
+    fn action_plan() -> impl Strategy<Value = ActionPlan> {
+        prop::collection::vec(
+            prop::sample::select(vec![Forward, Right, Backward, Left]),
+            0..8,
+        )
+        .prop_map(ActionPlan)
+    }
+
+    fn cw_rotation() -> impl Strategy<Value = CWRotation> {
+        prop::sample::select(vec![
+            CWRotation::Zero,
+            CWRotation::Ninety,
+            CWRotation::OneEighty,
+            CWRotation::TwoSeventy,
+        ])
+    }
+
+    proptest! {
+        #[test]
+        fn canonicalize_is_idempotent(action_plan in action_plan()) {
+            let canonical = action_plan.canonicalize();
+
+            prop_assert_eq!(canonical.canonicalize(), canonical);
+        }
+
+        #[test]
+        fn canonicalize_ignores_phase(mut action_plan in action_plan(), phase_shift in 0..8usize) {
+            let canonical = action_plan.canonicalize();
+
+            if !action_plan.is_empty() {
+                let len = action_plan.len();
+                action_plan.rotate_right(phase_shift % len);
+            }
+
+            prop_assert_eq!(action_plan.canonicalize(), canonical);
+        }
+
+        #[test]
+        fn canonicalize_ignores_mirroring(action_plan in action_plan()) {
+            prop_assert_eq!(action_plan.mirror().canonicalize(), action_plan.canonicalize());
+        }
+
+        #[test]
+        fn canonicalize_ignores_relabeling(action_plan in action_plan(), rotation in cw_rotation()) {
+            let relabeled = ActionPlan(action_plan.iter().map(rotation.to_combinator()).collect());
+
+            prop_assert_eq!(relabeled.canonicalize(), action_plan.canonicalize());
+        }
+    }
+
+    #[test]
+    fn code_round_trip() {
+        let plan = ActionPlan(vec![Forward, Right, Right, Backward, Left]);
+
+        assert_eq!(plan.to_code(), "FRRBL");
+        assert_eq!(ActionPlan::from_code("FRRBL"), Ok(plan.clone()));
+        assert_eq!(ActionPlan::from_code(" frr bl\n"), Ok(plan));
+        assert_eq!(ActionPlan::from_code("FRX"), Err('X'));
+        assert_eq!(ActionPlan::from_code(""), Ok(ActionPlan::default()));
+    }
+}
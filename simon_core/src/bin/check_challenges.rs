@@ -0,0 +1,44 @@
+//! Reports, for every level, whether its challenge thresholds match what the solver finds.
+//!
+//! `cargo run --bin check_challenges [level name...]`, exits non-zero if any level fails.
+
+use std::process::ExitCode;
+
+use simon_core::{
+    level::{SCENES, Scene},
+    solver::check_level,
+};
+
+fn main() -> ExitCode {
+    let names = std::env::args().skip(1).collect::<Vec<_>>();
+    let mut failed = 0;
+
+    for scene in SCENES.iter() {
+        let Scene::Level(level) = scene else {
+            continue;
+        };
+
+        if !names.is_empty()
+            && !names
+                .iter()
+                .any(|name| name.eq_ignore_ascii_case(level.name))
+        {
+            continue;
+        }
+
+        let report = check_level(level);
+
+        if !report.is_ok() {
+            failed += 1;
+        }
+
+        print!("{report}");
+    }
+
+    if failed > 0 {
+        eprintln!("{failed} level(s) have challenge thresholds that don't match the solver");
+        ExitCode::FAILURE
+    } else {
+        ExitCode::SUCCESS
+    }
+}
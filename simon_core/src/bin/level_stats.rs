@@ -0,0 +1,89 @@
+//! Measures how hard each level is from its solution space, to help order [`SCENES`].
+//!
+//! `cargo run --release --bin level_stats -- [--json] [--sort] [level name...]`, `--sort` lists the
+//! levels from easiest to hardest instead of in play order.
+
+use std::process::ExitCode;
+
+use serde::Serialize;
+use simon_core::{
+    level::{SCENES, Scene},
+    solver::{LevelStats, level_stats},
+};
+
+const USAGE: &str = "usage: level_stats [--json] [--sort] [level name...]";
+
+#[derive(Serialize)]
+struct Output {
+    #[serde(flatten)]
+    stats: LevelStats,
+    difficulty: f64,
+}
+
+fn main() -> ExitCode {
+    let mut json = false;
+    let mut sort = false;
+    let mut names = Vec::new();
+
+    for arg in std::env::args().skip(1) {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--sort" => sort = true,
+            "-h" | "--help" => {
+                eprintln!("{USAGE}");
+                return ExitCode::FAILURE;
+            }
+            _ => names.push(arg),
+        }
+    }
+
+    let mut stats = SCENES
+        .iter()
+        .filter_map(|scene| match scene {
+            Scene::Level(level)
+                if names.is_empty()
+                    || names
+                        .iter()
+                        .any(|name| name.eq_ignore_ascii_case(level.name)) =>
+            {
+                Some(level_stats(level))
+            }
+            _ => None,
+        })
+        .collect::<Vec<_>>();
+
+    if sort {
+        stats.sort_by(|a, b| a.difficulty().total_cmp(&b.difficulty()));
+    }
+
+    if json {
+        let output = stats
+            .into_iter()
+            .map(|stats| Output {
+                difficulty: stats.difficulty(),
+                stats,
+            })
+            .collect::<Vec<_>>();
+
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).expect("level stats are serialisable")
+        );
+
+        return ExitCode::SUCCESS;
+    }
+
+    let mut previous = None;
+
+    for stats in &stats {
+        print!("{stats}");
+
+        if previous.is_some_and(|previous| stats.difficulty() < previous) {
+            println!("  easier than the level before");
+        }
+
+        previous = Some(stats.difficulty());
+    }
+
+    ExitCode::SUCCESS
+}
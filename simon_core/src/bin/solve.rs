@@ -0,0 +1,170 @@
+//! Prints a level's smallest, fastest and slowest solutions.
+//!
+//! `cargo run --bin solve -- [--json] [--action-limit N] <level name | level file>`
+
+use std::process::ExitCode;
+
+use serde::Serialize;
+use simon_core::{
+    actions::ActionPlan,
+    level::{Level, SCENES, Scene},
+    level_file,
+    share_code::ShareCode,
+    solver::{
+        Solution, canonical_solution_count, enumerate_solutions, fastest_solutions,
+        slowest_solutions, smallest_solutions,
+    },
+};
+
+const USAGE: &str = "usage: solve [--json] [--action-limit N] <level name | level file>";
+
+struct Args {
+    json: bool,
+    action_limit: Option<usize>,
+    level: String,
+}
+
+fn parse_args() -> Result<Args, String> {
+    let mut json = false;
+    let mut action_limit = None;
+    let mut level = None;
+    let mut args = std::env::args().skip(1);
+
+    while let Some(arg) = args.next() {
+        match arg.as_str() {
+            "--json" => json = true,
+            "--action-limit" => {
+                let value = args.next().ok_or("--action-limit needs a number")?;
+                action_limit =
+                    Some(value.parse().map_err(|_| {
+                        format!("--action-limit should be a number, not '{value}'")
+                    })?);
+            }
+            "-h" | "--help" => return Err(USAGE.into()),
+            _ if level.is_none() => level = Some(arg),
+            _ => return Err(format!("unexpected argument '{arg}'\n{USAGE}")),
+        }
+    }
+
+    Ok(Args {
+        json,
+        action_limit,
+        level: level.ok_or(USAGE)?,
+    })
+}
+
+/// A level in [`SCENES`] by name or id, otherwise a level file at that path.
+fn load_level(name: &str) -> Result<Level, String> {
+    let scene = SCENES.iter().find_map(|scene| match scene {
+        Scene::Level(level)
+            if level.name.eq_ignore_ascii_case(name) || level.id.eq_ignore_ascii_case(name) =>
+        {
+            Some(level)
+        }
+        _ => None,
+    });
+
+    if let Some(level) = scene {
+        return Ok(level.clone());
+    }
+
+    let text = std::fs::read_to_string(name)
+        .map_err(|err| format!("'{name}' isn't a level name or a readable file: {err}"))?;
+
+    level_file::parse(&text).map_err(|err| format!("{name}: {err}"))
+}
+
+#[derive(Serialize)]
+struct SolutionOutput {
+    code: String,
+    commands: usize,
+    steps: usize,
+}
+
+#[derive(Serialize)]
+struct Output {
+    level: &'static str,
+    action_limit: usize,
+    solutions: usize,
+    canonical_solutions: usize,
+    smallest: Vec<SolutionOutput>,
+    fastest: Vec<SolutionOutput>,
+    slowest: Vec<SolutionOutput>,
+}
+
+fn print_text(output: &Output) {
+    println!(
+        "{} (action limit {}): {} solutions, {} canonical",
+        output.level, output.action_limit, output.solutions, output.canonical_solutions
+    );
+
+    for (title, solutions) in [
+        ("smallest", &output.smallest),
+        ("fastest", &output.fastest),
+        ("slowest", &output.slowest),
+    ] {
+        println!("{title}:");
+
+        for solution in solutions {
+            println!(
+                "  {:<24} {} commands, {} steps",
+                solution.code, solution.commands, solution.steps
+            );
+        }
+    }
+}
+
+fn main() -> ExitCode {
+    let args = match parse_args() {
+        Ok(args) => args,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    let mut level = match load_level(&args.level) {
+        Ok(level) => level,
+        Err(err) => {
+            eprintln!("{err}");
+            return ExitCode::FAILURE;
+        }
+    };
+
+    if let Some(action_limit) = args.action_limit {
+        level.action_limit = action_limit;
+    }
+
+    let solutions = enumerate_solutions(&level);
+    let outputs = |solutions: Vec<Solution>| -> Vec<SolutionOutput> {
+        solutions
+            .into_iter()
+            .map(|solution| SolutionOutput {
+                code: ShareCode::new(&level, &ActionPlan(solution.path)).to_string(),
+                commands: solution.solution_size,
+                steps: solution.steps,
+            })
+            .collect()
+    };
+
+    let output = Output {
+        level: level.name,
+        action_limit: level.action_limit,
+        solutions: solutions.len(),
+        canonical_solutions: canonical_solution_count(&solutions),
+        smallest: outputs(smallest_solutions(&solutions)),
+        fastest: outputs(fastest_solutions(&solutions)),
+        slowest: outputs(slowest_solutions(&solutions)),
+    };
+
+    if args.json {
+        println!(
+            "{}",
+            serde_json::to_string_pretty(&output).expect("solver output is serialisable")
+        );
+    } else {
+        print_text(&output);
+    }
+
+    ExitCode::SUCCESS
+}
@@ -0,0 +1,273 @@
+use std::{
+    any::Any,
+    collections::{BTreeMap, BTreeSet},
+    fmt,
+    sync::Arc,
+};
+
+use serde::{Deserialize, Serialize};
+
+use crate::{actions::Action, level::Level};
+
+/// What happened during a run that completed a level, for challenges to judge.
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+pub struct RunSummary {
+    pub plan: Vec<Action>,
+    pub steps: usize,
+}
+
+impl RunSummary {
+    pub fn commands(&self) -> usize {
+        self.plan.len()
+    }
+}
+
+/// An optional goal on a level, tracked in the [`ChallengeRecord`] under its [`Challenge::id`].
+pub trait Challenge: Any + fmt::Debug + Send + Sync {
+    /// Stable key for the save, two challenges on the same level must not share one.
+    fn id(&self) -> String;
+
+    /// e.g. "Alacrity: Take 5 or fewer steps".
+    fn description(&self) -> String;
+
+    fn evaluate(&self, run: &RunSummary) -> bool;
+
+    /// The player's best result towards this challenge, shown alongside it.
+    fn best(&self, _record: &ChallengeRecord) -> Option<String> {
+        None
+    }
+}
+
+pub type SharedChallenge = Arc<dyn Challenge>;
+
+#[derive(Debug, Clone, Default, PartialEq, Eq, Serialize, Deserialize)]
+pub struct ChallengeRecord {
+    /// Whether each of the level's challenges has been met, keyed by [`Challenge::id`].
+    pub challenges: BTreeMap<String, bool>,
+    pub level_completed: bool,
+    // Best results, these postdate the challenge flags so older saves won't have them
+    #[serde(default)]
+    pub fewest_commands: Option<usize>,
+    #[serde(default)]
+    pub fewest_steps: Option<usize>,
+    #[serde(default)]
+    pub most_steps: Option<usize>,
+    #[serde(default)]
+    pub attempts: usize,
+    #[serde(default)]
+    pub deaths: usize,
+    /// Seconds since the Unix epoch.
+    #[serde(default)]
+    pub first_completed: Option<u64>,
+    #[serde(default)]
+    pub hints_used: usize,
+    /// Challenges that have only been met after using a hint on the level.
+    #[serde(default)]
+    pub assisted: BTreeSet<String>,
+}
+
+impl ChallengeRecord {
+    /// Folds a completed run into the best results.
+    pub fn record_completion(&mut self, commands: usize, steps: usize, timestamp: u64) {
+        self.level_completed = true;
+        self.fewest_commands = Some(
+            self.fewest_commands
+                .map_or(commands, |best| best.min(commands)),
+        );
+        self.fewest_steps = Some(self.fewest_steps.map_or(steps, |best| best.min(steps)));
+        self.most_steps = Some(self.most_steps.map_or(steps, |best| best.max(steps)));
+        self.first_completed.get_or_insert(timestamp);
+    }
+
+    /// Marks a challenge as met, meeting it without a hint clears an earlier assisted completion.
+    pub fn complete_challenge(&mut self, id: String, assisted: bool) {
+        if !assisted {
+            self.assisted.remove(&id);
+        } else if !self.challenges.get(&id).copied().unwrap_or_default() {
+            self.assisted.insert(id.clone());
+        }
+
+        self.challenges.insert(id, true);
+    }
+
+    pub fn is_assisted(&self, challenge: &dyn Challenge) -> bool {
+        self.assisted.contains(&challenge.id())
+    }
+
+    fn is_unassisted(&self, id: &str) -> bool {
+        self.challenges.get(id).copied().unwrap_or_default() && !self.assisted.contains(id)
+    }
+
+    pub fn is_completed(&self, challenge: &dyn Challenge) -> bool {
+        self.challenges
+            .get(&challenge.id())
+            .copied()
+            .unwrap_or_default()
+    }
+
+    /// Whether every challenge on `level` has been met.
+    pub fn all_completed(&self, level: &Level) -> bool {
+        level
+            .challenges
+            .iter()
+            .all(|challenge| self.is_completed(&**challenge))
+    }
+
+    /// Combines progress made on another machine, keeping the best of both. Counts can't be told
+    /// apart from ones already merged, so the larger is kept and merging twice changes nothing.
+    pub fn merge(&mut self, other: &ChallengeRecord) {
+        fn best<T>(a: Option<T>, b: Option<T>, pick: fn(T, T) -> T) -> Option<T> {
+            match (a, b) {
+                (Some(a), Some(b)) => Some(pick(a, b)),
+                (a, b) => a.or(b),
+            }
+        }
+
+        // Before the flags are combined, so completions are still paired with their assistance
+        self.assisted = self
+            .assisted
+            .union(&other.assisted)
+            .filter(|id| !self.is_unassisted(id) && !other.is_unassisted(id))
+            .cloned()
+            .collect();
+
+        for (id, completed) in &other.challenges {
+            *self.challenges.entry(id.clone()).or_default() |= completed;
+        }
+
+        self.level_completed |= other.level_completed;
+        self.fewest_commands = best(self.fewest_commands, other.fewest_commands, usize::min);
+        self.fewest_steps = best(self.fewest_steps, other.fewest_steps, usize::min);
+        self.most_steps = best(self.most_steps, other.most_steps, usize::max);
+        self.attempts = self.attempts.max(other.attempts);
+        self.deaths = self.deaths.max(other.deaths);
+        self.first_completed = best(self.first_completed, other.first_completed, u64::min);
+        self.hints_used = self.hints_used.max(other.hints_used);
+    }
+
+    /// e.g. "best: 4 cmds / 9 steps", if the level has been completed.
+    pub fn best_summary(&self) -> Option<String> {
+        Some(format!(
+            "best: {} cmds / {} steps",
+            self.fewest_commands?, self.fewest_steps?
+        ))
+    }
+}
+
+/// Alacrity, finish in at most this many steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FewestSteps(pub usize);
+
+impl Challenge for FewestSteps {
+    fn id(&self) -> String {
+        "steps".into()
+    }
+
+    fn description(&self) -> String {
+        format!("Alacrity: Take {} or fewer steps", self.0)
+    }
+
+    fn evaluate(&self, run: &RunSummary) -> bool {
+        run.steps <= self.0
+    }
+
+    fn best(&self, record: &ChallengeRecord) -> Option<String> {
+        Some(format!("best: {} steps", record.fewest_steps?))
+    }
+}
+
+/// Parsimony, finish with at most this many commands.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct FewestCommands(pub usize);
+
+impl Challenge for FewestCommands {
+    fn id(&self) -> String {
+        "commands".into()
+    }
+
+    fn description(&self) -> String {
+        format!("Parsimony: Use {} or fewer commands", self.0)
+    }
+
+    fn evaluate(&self, run: &RunSummary) -> bool {
+        run.commands() <= self.0
+    }
+
+    fn best(&self, record: &ChallengeRecord) -> Option<String> {
+        Some(format!("best: {} cmds", record.fewest_commands?))
+    }
+}
+
+/// Circuity, finish in at least this many steps.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MostSteps(pub usize);
+
+impl Challenge for MostSteps {
+    fn id(&self) -> String {
+        "waste".into()
+    }
+
+    fn description(&self) -> String {
+        format!("Circuity: Take {} or more steps", self.0)
+    }
+
+    fn evaluate(&self, run: &RunSummary) -> bool {
+        run.steps >= self.0
+    }
+
+    fn best(&self, record: &ChallengeRecord) -> Option<String> {
+        Some(format!("best: {} steps", record.most_steps?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use Action::*;
+
+    #[test]
+    fn evaluation() {
+        let run = RunSummary {
+            plan: vec![Forward, Right, Forward],
+            steps: 7,
+        };
+
+        assert!(FewestSteps(7).evaluate(&run));
+        assert!(!FewestSteps(6).evaluate(&run));
+        assert!(FewestCommands(3).evaluate(&run));
+        assert!(!FewestCommands(2).evaluate(&run));
+        assert!(MostSteps(7).evaluate(&run));
+        assert!(!MostSteps(8).evaluate(&run));
+    }
+
+    #[test]
+    fn merge_is_idempotent() {
+        let mut record = ChallengeRecord {
+            attempts: 3,
+            deaths: 2,
+            hints_used: 1,
+            ..Default::default()
+        };
+        record.record_completion(4, 9, 100);
+        record.complete_challenge("steps".into(), true);
+        record.complete_challenge("commands".into(), false);
+
+        let mut merged = record.clone();
+        merged.merge(&record);
+        assert_eq!(merged, record);
+
+        let mut other = ChallengeRecord {
+            attempts: 5,
+            deaths: 1,
+            ..Default::default()
+        };
+        other.record_completion(6, 7, 50);
+
+        merged.merge(&other);
+        let once = merged.clone();
+        merged.merge(&other);
+        assert_eq!(merged, once);
+        assert_eq!((merged.attempts, merged.deaths), (5, 2));
+    }
+}
@@ -0,0 +1,1070 @@
+use std::{
+    any::Any,
+    collections::HashMap,
+    sync::{Arc, LazyLock},
+};
+
+use crate::{
+    actions::{Action, CWRotation},
+    challenge::{Challenge, FewestCommands, FewestSteps, MostSteps, SharedChallenge},
+    player::Player,
+};
+
+#[derive(Debug, Clone)]
+pub enum Scene {
+    Start,
+    Dialogue,
+    Level(Level),
+    Loop,
+    Finish,
+}
+
+impl From<Level> for Scene {
+    fn from(value: Level) -> Self {
+        Self::Level(value)
+    }
+}
+
+#[derive(Debug, Clone, Default)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Resource))]
+pub struct Level {
+    pub tiles: HashMap<(i32, i32), Tile>,
+    /// Stable key for the save, unlike `name` this must never change once a level has shipped.
+    pub id: &'static str,
+    pub name: &'static str,
+    pub actions: Vec<Action>,
+    pub action_limit: usize,
+    pub challenges: Vec<SharedChallenge>,
+    /// Scales how far the camera sits from the tiles, above 1 pulls back from the fitted framing.
+    pub zoom: Option<f32>,
+}
+
+impl Level {
+    pub fn get(&self, position: (i32, i32)) -> Option<&Tile> {
+        self.tiles.get(&position)
+    }
+
+    pub fn builder() -> LevelBuilder {
+        LevelBuilder::new()
+    }
+
+    /// This level rotated or reflected by `symmetry`, with starts, rotation blocks and actions
+    /// rewritten so every plan plays out the same as its [`Symmetry::action`] image does here.
+    pub fn transformed(&self, symmetry: Symmetry) -> Level {
+        Level {
+            tiles: self
+                .tiles
+                .iter()
+                .map(|(position, tile)| (symmetry.position(*position), symmetry.tile(*tile)))
+                .collect(),
+            actions: self
+                .actions
+                .iter()
+                .map(|action| symmetry.action(*action))
+                .collect(),
+            challenges: self.challenges.clone(),
+            ..*self
+        }
+    }
+
+    /// The first challenge of type `T` on this level.
+    pub fn challenge<T: Challenge>(&self) -> Option<&T> {
+        self.challenges
+            .iter()
+            .find_map(|challenge| (&**challenge as &dyn Any).downcast_ref::<T>())
+    }
+
+    pub fn command_challenge(&self) -> Option<usize> {
+        self.challenge::<FewestCommands>()
+            .map(|challenge| challenge.0)
+    }
+
+    pub fn step_challenge(&self) -> Option<usize> {
+        self.challenge::<FewestSteps>().map(|challenge| challenge.0)
+    }
+
+    pub fn waste_challenge(&self) -> Option<usize> {
+        self.challenge::<MostSteps>().map(|challenge| challenge.0)
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub enum Tile {
+    Start(CWRotation),
+    Basic,
+    Ice,
+    Wall,
+    CWRot,
+    CCWRot,
+    Finish,
+}
+
+impl Tile {
+    pub const DEFAULT_START: Self = Tile::Start(CWRotation::Zero);
+
+    pub fn rotation(self) -> CWRotation {
+        match self {
+            Tile::Start(rot) => rot,
+            _ => {
+                tracing::error!(tile =?self, "Tile::rotation called on non-rotatable tile");
+                CWRotation::default()
+            }
+        }
+    }
+}
+
+pub struct LevelBuilder(Level);
+
+impl LevelBuilder {
+    pub fn new() -> Self {
+        Self(Level {
+            tiles: HashMap::new(),
+            id: "unnamed",
+            name: "Unnamed",
+            actions: vec![
+                Action::Forward,
+                Action::Right,
+                Action::Backward,
+                Action::Left,
+            ],
+            action_limit: 1,
+            challenges: Vec::new(),
+            zoom: None,
+        })
+    }
+
+    pub fn block(mut self, lower: (i32, i32), upper: (i32, i32), tile: Tile) -> Self {
+        for x in lower.0..=upper.0 {
+            for y in lower.1..=upper.1 {
+                self.0.tiles.insert((x, y), tile);
+            }
+        }
+
+        self
+    }
+
+    pub fn insert(mut self, tiles: impl IntoIterator<Item = ((i32, i32), Tile)>) -> Self {
+        for (position, tile) in tiles {
+            self.0.tiles.insert(position, tile);
+        }
+        self
+    }
+
+    pub fn remove(mut self, tiles: impl IntoIterator<Item = (i32, i32)>) -> Self {
+        for position in tiles {
+            self.0.tiles.remove(&position);
+        }
+        self
+    }
+
+    pub fn actions(mut self, actions: impl IntoIterator<Item = Action>) -> Self {
+        self.0.actions = Vec::from_iter(actions);
+        self
+    }
+
+    pub fn action_limit(mut self, limit: usize) -> Self {
+        self.0.action_limit = limit;
+        self
+    }
+
+    pub fn zoom(mut self, zoom: f32) -> Self {
+        self.0.zoom = Some(zoom);
+        self
+    }
+
+    /// Adds a challenge, replacing any existing one with the same id.
+    pub fn challenge(mut self, challenge: impl Challenge) -> Self {
+        let id = challenge.id();
+        self.0.challenges.retain(|existing| existing.id() != id);
+        self.0.challenges.push(Arc::new(challenge));
+        self
+    }
+
+    pub fn command_challenge(self, challenge: usize) -> Self {
+        self.challenge(FewestCommands(challenge))
+    }
+
+    pub fn step_challenge(self, challenge: usize) -> Self {
+        self.challenge(FewestSteps(challenge))
+    }
+
+    pub fn waste_challenge(self, challenge: usize) -> Self {
+        self.challenge(MostSteps(challenge))
+    }
+
+    pub fn id(mut self, id: &'static str) -> Self {
+        self.0.id = id;
+        self
+    }
+
+    pub fn name(mut self, name: &'static str) -> Self {
+        self.0.name = name;
+        self
+    }
+
+    pub fn build(self) -> Level {
+        self.0
+    }
+}
+
+impl Default for LevelBuilder {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+pub static SCENES: LazyLock<Vec<Scene>> = LazyLock::new(|| {
+    vec![
+        LevelBuilder::new()
+            .id("lost")
+            .name("Lost")
+            .action_limit(1)
+            .actions([Action::Forward])
+            .block((-2, 0), (2, 0), Tile::Basic)
+            .insert([((-2, 0), Tile::DEFAULT_START), ((2, 0), Tile::Finish)])
+            .insert(from_pictogram(&["🧑🟦🟦🟦🟩"]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("arbitrary")
+            .name("Arbitrary")
+            .action_limit(2)
+            .actions([Action::Forward, Action::Right])
+            .insert(from_pictogram(&[
+                "🧑🟦🟦🟦",
+                "🟦🟦🟦🟦",
+                "🟦🟦🟦🟦",
+                "🟦🟦🟦🟩",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("pothole")
+            .name("Pothole")
+            .action_limit(2)
+            .insert(from_pictogram(&[
+                "🟦🟦🟦🟩",
+                "🟦🟦🟦⬛",
+                "🟦🟦🟦🟦",
+                "🧑🟦🟦🟦",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("noise")
+            .name("Noise")
+            .action_limit(3)
+            .insert(transform(
+                ANTI_CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🟦🟦🟦🟦🟦",
+                    "🟦⬛⬛🟦🟩",
+                    "🟦⬛🟦🟦🟦",
+                    "👩🟦🟦🟦🟦",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("obstructions")
+            .name("Obstructions")
+            .action_limit(3)
+            .command_challenge(2)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬛⬜⬛⬛",
+                    "⬛🧑🟦🟦⬛",
+                    "⬜🟦⬜🟦⬜",
+                    "⬛🟦🟦🟩⬛",
+                    "⬛⬛⬜⬛⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("choices")
+            .name("Choices")
+            .action_limit(6)
+            .command_challenge(3)
+            .step_challenge(8)
+            .waste_challenge(22)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🧑⬜⬛⬛⬜⬛⬛",
+                    "🟦🟦🟦🟦🟦🟦🟩",
+                    "⬛⬛🟦⬜🟦⬛⬛",
+                    "🟦🟦🟦🟦🟦🟦🟩",
+                    "🟦⬜⬛⬛⬜⬛⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("precarious")
+            .name("Precarious")
+            .action_limit(6)
+            .command_challenge(4)
+            .step_challenge(8)
+            .waste_challenge(13)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🟦🟦⬜⬛⬛⬛⬛",
+                    "🟦🟦🟦🟦⬛⬛⬛",
+                    "⬛⬛⬜🟦🟦🟦🟩",
+                    "🟦🟦🟦🟦⬛⬛⬛",
+                    "🧑🟦⬜⬛⬛⬛⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("hook")
+            .name("Hook")
+            .action_limit(5)
+            .command_challenge(4)
+            .step_challenge(7)
+            .waste_challenge(14)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬛⬛⬛⬜⬛",
+                    "⬜⬜🟦🟦🟦⬜",
+                    "🧑🟦🟦⬜🟩🟦",
+                    "⬛⬛⬜⬛⬛⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("crucible")
+            .name("Crucible")
+            .action_limit(8)
+            .command_challenge(7)
+            .step_challenge(14)
+            .waste_challenge(23)
+            .insert(from_pictogram(&[
+                "⬛⬛🟦🟦🟦",
+                "⬜🟦🟦⬜🟦",
+                "🧑🟦⬜🟩🟦",
+                "⬛⬜⬛⬛⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("rift")
+            .name("Rift")
+            .action_limit(7)
+            .command_challenge(6)
+            .step_challenge(14)
+            .waste_challenge(24)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🟦⬜🟦⬜🟦⬜🟦",
+                "🟦🟦🟦🟦🟦🟦🟦",
+                "⬜🟦🧑⬛⬜🟦⬜",
+                "🟦🟦🟦⬛🟦🟦🟦",
+                "⬜🟦⬜⬛🟩🟦⬜",
+                "🟦🟦🟦🟦🟦🟦🟦",
+                "⬜🟦⬜🟦⬜🟦⬜",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("overshoot")
+            .name("Overshoot")
+            .action_limit(4)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛⬛🟦🟦",
+                "🧑🏂🏂🟦",
+                "⬛⬛🏂⬛",
+                "⬛⬛🟩⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("glide")
+            .name("Glide")
+            .action_limit(4)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛⬜⬛⬛⬛",
+                "⬛🏂🏂⬜⬛",
+                "⬜🏂🏂🏂🧑",
+                "⬛⬛🏂⬛⬛",
+                "⬛⬛🟩⬛⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("loops")
+            .name("Loops")
+            .action_limit(5)
+            .command_challenge(4)
+            .step_challenge(9)
+            .waste_challenge(13)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛⬛⬛🟦🏂🟦",
+                "⬜🟦⬜🟦🟩🏂",
+                "🟦🟦🏂🏂🏂🟦",
+                "⬜🧑⬜⬛⬛⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("gauntlet")
+            .name("Gauntlet")
+            .action_limit(5)
+            .command_challenge(5)
+            .step_challenge(7)
+            .waste_challenge(9)
+            .insert(transform(
+                ANTI_CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬜⬛⬛⬛⬛",
+                    "⬛🏂🏂🏂⬜⬛",
+                    "⬛🏂🏂🏂🏂⬛",
+                    "⬜🏂🧑🏂🏂⬜",
+                    "⬜🏂🏂🏂🏂⬛",
+                    "⬛⬜🏂🏂⬜⬛",
+                    "⬛🏂🏂🏂🏂⬛",
+                    "⬜🏂🏂🏂🏂⬛",
+                    "⬛🟩🏂⬜🏂⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("esky")
+            .name("Esky")
+            .action_limit(5)
+            .command_challenge(4)
+            .step_challenge(8)
+            .waste_challenge(15)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬜⬜⬜⬜⬜⬜⬜⬜",
+                "⬜🧑🏂🏂🏂🏂🏂⬜",
+                "⬜⬛🟦🟦🏂🏂🏂⬜",
+                "⬜🟦🏂🏂🏂🟦🏂⬜",
+                "⬜🏂🏂🟦🟩🏂🏂⬜",
+                "⬜🟦🏂🟦🏂🟦🏂⬜",
+                "⬜🏂🟦🏂🏂🏂🏂⬜",
+                "⬜⬜⬜⬜⬜⬜⬜⬜",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("divert")
+            .name("Divert")
+            .action_limit(1)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🧑🟦🔃",
+                "⬛⬛🟦",
+                "⬛⬛🟩",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("pivot")
+            .name("Pivot")
+            .action_limit(4)
+            .waste_challenge(4)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🧑🔃🟩",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("twirl")
+            .name("Twirl")
+            .action_limit(4)
+            .waste_challenge(10)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🧑🔃🔃🔃🔃🔃🟩",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("dizzy")
+            .name("Dizzy")
+            .action_limit(4)
+            .insert(transform(
+                CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬛⬛⬛🟩",
+                    "⬛⬛⬛⬛🔃",
+                    "⬛⬛⬛⬛🔃",
+                    "⬛⬛⬛⬛🔃",
+                    "🧑🔃🔃🔃🟦",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("zigzag")
+            .name("ZigZag")
+            .action_limit(8)
+            .command_challenge(4)
+            // .step_challenge(10)
+            .waste_challenge(18)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🧑🔄🔄⬛⬛⬛⬛",
+                    "⬛⬛🔄⬛⬛⬛⬛",
+                    "⬛⬛🔄🔄🔄⬛⬛",
+                    "⬛⬛⬛⬛🔄⬛⬛",
+                    "⬛⬛⬛⬛🔄🔄🟩",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("binary")
+            .name("Binary")
+            .action_limit(6)
+            .command_challenge(2)
+            .waste_challenge(19)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🧑🔄🔃🔄🔃🔄🔃🟩",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("two-step")
+            .name("Two-Step")
+            .action_limit(4)
+            .waste_challenge(17)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🧑🔄🔄🔃🔃🔄🔄🟩",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("chess")
+            .name("Chess")
+            .action_limit(6)
+            .command_challenge(4)
+            .waste_challenge(25)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🔄🔃🔄🔃🔄🔃🟩",
+                    "🔃🔄🔃🔄🔃🔄🔃",
+                    "🔄🔃🔄🔃🔄🔃🔄",
+                    "🧑🔄🔃🔄🔃🔄🔃",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("progress")
+            .name("Progress")
+            .actions([Action::Forward, Action::Left, Action::Right])
+            .action_limit(1)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬛⬜",
+                    "🟩🧑🔄",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("support")
+            .name("Support")
+            .action_limit(3)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬜",
+                    "🧑🔄",
+                    "⬛🟦",
+                    "🟩🟦",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("snail")
+            .name("Snail")
+            .action_limit(3)
+            .insert(transform(
+                ANTI_CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬜",
+                    "🧑🔄",
+                    "🟦🟦",
+                    "⬛🔃",
+                    "⬛🟩",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("trapped")
+            .name("Trapped")
+            .action_limit(5)
+            .command_challenge(3)
+            .step_challenge(8)
+            .waste_challenge(14)
+            .insert(transform(
+                CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬛⬜🟦⬛⬛",
+                    "🧑🔄🟦⬛🟦",
+                    "⬛⬜🟦🟦🟦",
+                    "⬛⬛⬛⬛🟩",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("restricted")
+            .name("Restricted")
+            .actions([Action::Forward, Action::Backward, Action::Right])
+            .action_limit(4)
+            .command_challenge(2)
+            .waste_challenge(10)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🟩🟦⬛",
+                    "🟦🧑🔄",
+                    "⬛🔄⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("swirl")
+            .name("Swirl")
+            .actions([Action::Forward, Action::Backward, Action::Right])
+            .action_limit(5)
+            .command_challenge(3)
+            .step_challenge(7)
+            .waste_challenge(16)
+            .insert(transform(
+                DOUBLE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🟩🟦⬛⬛⬛⬛",
+                    "⬛🟦⬛⬛⬛⬛",
+                    "⬛🟦🟦⬛⬜⬛",
+                    "⬛⬜🧒🔄🔄⬛",
+                    "⬛⬛🔄⬛🔄⬛",
+                    "⬛⬛🔄🔄🔄⬜",
+                    "⬛⬛⬜⬛⬛⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("squeeze")
+            .name("Squeeze")
+            .action_limit(6)
+            .command_challenge(5)
+            .step_challenge(11)
+            .waste_challenge(23)
+            .insert(transform(
+                CLOCKWISE,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "🟩🟦🔄",
+                    "⬜🟦⬜",
+                    "⬛🔃⬛",
+                    "🟦🔃🟦",
+                    "⬛⬜🔄",
+                    "⬛🟦🧑",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("popsicle")
+            .name("Popsicle")
+            .action_limit(7)
+            .command_challenge(5)
+            .step_challenge(10)
+            .waste_challenge(22)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬜🔄🟦🔃⬜",
+                    "⬛🟦⬛🔃⬜",
+                    "⬜🔄🟦🔃⬜",
+                    "⬛🟦⬛🟦⬜",
+                    "⬜🔄⬛🔃⬜",
+                    "⬛🧑⬛🟩⬛",
+                ]),
+            ))
+            .build()
+            .into(),
+        // LevelBuilder::new()
+        //     .name("Spinors")
+        //     .action_limit(5)
+        //     .command_challenge(2)
+        //     .step_challenge(10)
+        //     .waste_challenge(36)
+        //     .insert(from_pictogram(&[
+        //         #[rustfmt::ignore]
+        //         "🔃🔃🔄🟩",
+        //         "🔃🔃🔄🟦",
+        //         "🔄🔄🔃🔄",
+        //         "🔃🔄🔃🔄",
+        //         "🟦🔄🔃🔃",
+        //         "🧑🔃🔄🔃",
+        //     ]))
+        //     .build()
+        //     .into(),
+        LevelBuilder::new()
+            .id("blizzard")
+            .name("Blizzard")
+            .action_limit(8)
+            .command_challenge(3)
+            .step_challenge(8)
+            .waste_challenge(70)
+            .insert(transform(
+                IDENTITY,
+                from_pictogram(&[
+                    #[rustfmt::ignore]
+                    "⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜",
+                    "⬜🔄🏂🏂🏂🏂🏂🏂🏂🔄⬜",
+                    "⬜🏂🔄🏂🏂🏂🏂🏂🟦🏂⬜",
+                    "⬜🏂🏂🔃🏂🟦🔃🏂🏂🏂⬜",
+                    "⬜🏂🏂🏂🧑⬜🟩🟦🏂🏂⬜",
+                    "⬜🏂🏂🔃🏂🟦🔃🏂🏂🏂⬜",
+                    "⬜🏂🟦🏂🏂🏂🏂🏂🏂🟦⬜",
+                    "⬜🔄🏂🏂🔄⬜🏂🏂🔄🔄⬜",
+                    "⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜⬜",
+                ]),
+            ))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("mirror")
+            .name("Mirror")
+            .action_limit(1)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🧑🟦🟦🟩",
+                "⬛⬛⬛⬛",
+                "🟩🟦🟦👩",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("duality")
+            .name("Duality")
+            .action_limit(3)
+            // .command_challenge(3)
+            // .step_challenge(6)
+            // .waste_challenge(31)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛🟦🏂🟩",
+                "⬛🟦🏂🟦",
+                "🧑🏂🏂⬜",
+                "⬜🏂🏂👩",
+                "🟦🏂🟦⬛",
+                "🟩🏂🟦⬛",
+            ]))
+            .build()
+            .into(),
+        // Scene::Start,
+        // Forward, Forward, Right, Backward, Right,
+        LevelBuilder::new()
+            .id("unnamed")
+            .name("Unnamed")
+            .action_limit(5)
+            .command_challenge(5)
+            // .step_challenge(6)
+            // .waste_challenge(31)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "🧑🟩⬜⬛⬛",
+                "🟦🟦🟦🟩⬛",
+                "🟦⬜🟦🟦⬜",
+                "🟩⬛🟦🟦👩",
+            ]))
+            .build()
+            .into(),
+        // Scene::Loop,
+        LevelBuilder::new()
+            .id("convergence")
+            .name("Convergence")
+            .action_limit(6)
+            .command_challenge(4)
+            .step_challenge(11)
+            .waste_challenge(31)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬜⬛⬛⬛⬛⬛",
+                "🧑⬜⬜⬜⬛⬛",
+                "🟦🟦🟦🟦⬜⬛",
+                "🟦🟦🟦🟦🟦🟩",
+                "🟦🟦🟦🟦⬜⬛",
+                "🧑⬜⬜⬜⬛⬛",
+                "⬜⬛⬛⬛⬛⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("perpendicular")
+            .name("Perpendicular")
+            .action_limit(6)
+            .command_challenge(4)
+            .step_challenge(7)
+            .waste_challenge(15)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬜⬜⬜⬜⬜⬜⬜⬜⬜",
+                "⬜🟩🏂🏂⬜🏂🏂🟩⬜",
+                "⬜🏂🏂🏂🏂🏂⬜🏂⬜",
+                "⬜🏂⬜🏂🏂🏂🏂🏂⬜",
+                "⬜🧑🏂🏂⬜🏂🏂🏂⬜",
+                "⬜🏂⬜🏂🏂🏂⬜🏂⬜",
+                "⬜🏂🏂⬜🏂🏂🏂🏂⬜",
+                "⬜⬜🏂🏂🧓🏂🏂⬜⬜",
+                "⬜⬜⬜⬜⬜⬜⬜⬜⬜",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("samsara")
+            .name("Samsara")
+            .action_limit(6)
+            .command_challenge(3)
+            .step_challenge(11)
+            .waste_challenge(55)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛⬜⬛⬛⬜⬛⬜⬛⬛⬜⬛",
+                "⬜🟩🏂🏂🔃🏂🔃🏂🏂🔄⬜",
+                "⬛🏂🏂🏂🏂🏂🏂🏂🏂🏂⬛",
+                "⬛🏂🏂🏂🔄🏂🏂🏂🏂🏂⬛",
+                "⬜🔃🏂🏂🧓🏂🧑🔄🏂🔄⬜",
+                "⬛🏂🏂🏂🏂⬛🏂🏂🏂🏂⬛",
+                "⬜🔃🏂🔄👩🏂🧒🏂🏂🔄⬜",
+                "⬛🏂🏂🏂🏂🏂🔄🏂🏂🏂⬛",
+                "⬛🏂🏂🏂🏂🏂🏂🏂🏂🏂⬛",
+                "⬜🏂🏂🏂🔄🏂🔄🏂🏂🟩⬜",
+                "⬛⬜⬛⬛⬜⬛⬜⬛⬛⬜⬛",
+            ]))
+            .build()
+            .into(),
+        LevelBuilder::new()
+            .id("transcendence")
+            .name("Transcendence")
+            .action_limit(6)
+            .command_challenge(6)
+            .step_challenge(18)
+            .waste_challenge(23)
+            .insert(from_pictogram(&[
+                #[rustfmt::ignore]
+                "⬛⬛⬛⬛⬛⬛⬛🧒⬛⬛⬛⬛⬛⬛⬛",
+                "⬛⬛⬛⬛⬛⬛🧒🟦🧒⬛⬛⬛⬛⬛⬛",
+                "⬛⬛⬛⬛⬛🧒🟦🟦🟦🧒⬛⬛⬛⬛⬛",
+                "⬛⬛⬛⬜🧒🟦🟦🟦🟦🟦🧒⬜⬛⬛⬛",
+                "⬛⬛⬛🧑⬜🟦🟦🟦🟦🟦⬜👩⬛⬛⬛",
+                "⬛⬛🧑🟦🟦⬜🟦🟦🟦⬜🟦🟦👩⬛⬛",
+                "⬛🧑🟦🟦🟦🟦⬜🟦⬜🟦🟦🟦🟦👩⬛",
+                "🧑🟦🟦🟦🟦🟦🟦🟩🟦🟦🟦🟦🟦🟦👩",
+                "⬛🧑🟦🟦🟦🟦⬜🟦⬜🟦🟦🟦🟦👩⬛",
+                "⬛⬛🧑🟦🟦⬜🟦🟦🟦⬜🟦🟦👩⬛⬛",
+                "⬛⬛⬛🧑⬜🟦🟦🟦🟦🟦⬜👩⬛⬛⬛",
+                "⬛⬛⬛⬜🧓🟦🟦🟦🟦🟦🧓⬜⬛⬛⬛",
+                "⬛⬛⬛⬛⬛🧓🟦🟦🟦🧓⬛⬛⬛⬛⬛",
+                "⬛⬛⬛⬛⬛⬛🧓🟦🧓⬛⬛⬛⬛⬛⬛",
+                "⬛⬛⬛⬛⬛⬛⬛🧓⬛⬛⬛⬛⬛⬛⬛",
+            ]))
+            .build()
+            .into(),
+    ]
+});
+
+/// One of the eight rotations and reflections of the board, as a matrix acting on positions.
+///
+/// Transforming the tiles, the players and the plan together plays out the same way, so if a level
+/// is unchanged by a symmetry (up to moving it) a plan and its transformed copy have the same result.
+/// Facings turn with the board, so plans only change under reflections, which swap left and right.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Symmetry([[i32; 2]; 2]);
+
+impl Symmetry {
+    pub const IDENTITY: Symmetry = Symmetry([[1, 0], [0, 1]]);
+
+    pub const ALL: [Symmetry; 8] = [
+        Symmetry::IDENTITY,
+        Symmetry([[0, -1], [1, 0]]),
+        Symmetry([[-1, 0], [0, -1]]),
+        Symmetry([[0, 1], [-1, 0]]),
+        Symmetry([[1, 0], [0, -1]]),
+        Symmetry([[-1, 0], [0, 1]]),
+        Symmetry([[0, 1], [1, 0]]),
+        Symmetry([[0, -1], [-1, 0]]),
+    ];
+
+    pub fn position(self, (x, y): (i32, i32)) -> (i32, i32) {
+        let [[a, b], [c, d]] = self.0;
+        (a * x + b * y, c * x + d * y)
+    }
+
+    pub fn is_reflection(self) -> bool {
+        let [[a, b], [c, d]] = self.0;
+        a * d - b * c < 0
+    }
+
+    /// The direction a player with `rotation` faces, matching
+    /// [`run_simulation_step`](crate::simulation::run_simulation_step).
+    fn direction(rotation: CWRotation) -> (i32, i32) {
+        match rotation {
+            CWRotation::Zero => (1, 0),
+            CWRotation::Ninety => (0, 1),
+            CWRotation::OneEighty => (-1, 0),
+            CWRotation::TwoSeventy => (0, -1),
+        }
+    }
+
+    /// Actions are relative to the player's facing, so only reflections change them.
+    pub fn action(self, action: Action) -> Action {
+        match action {
+            Action::Left if self.is_reflection() => Action::Right,
+            Action::Right if self.is_reflection() => Action::Left,
+            action => action,
+        }
+    }
+
+    pub fn rotation(self, rotation: CWRotation) -> CWRotation {
+        match self.position(Symmetry::direction(rotation)) {
+            (1, 0) => CWRotation::Zero,
+            (0, 1) => CWRotation::Ninety,
+            (-1, 0) => CWRotation::OneEighty,
+            _ => CWRotation::TwoSeventy,
+        }
+    }
+
+    pub fn tile(self, tile: Tile) -> Tile {
+        match tile {
+            Tile::Start(rotation) => Tile::Start(self.rotation(rotation)),
+            // Reflecting turns clockwise into anticlockwise
+            Tile::CWRot if self.is_reflection() => Tile::CCWRot,
+            Tile::CCWRot if self.is_reflection() => Tile::CWRot,
+            tile => tile,
+        }
+    }
+
+    pub fn player(self, player: Player) -> Player {
+        Player {
+            position: self.position(player.position),
+            rotation: self.rotation(player.rotation),
+        }
+    }
+
+    /// Whether `level` looks the same after this symmetry and a translation.
+    pub fn preserves(self, level: &Level) -> bool {
+        let corner = |positions: &mut dyn Iterator<Item = (i32, i32)>| {
+            positions.fold((i32::MAX, i32::MAX), |(min_x, min_y), (x, y)| {
+                (min_x.min(x), min_y.min(y))
+            })
+        };
+
+        let (x, y) = corner(&mut level.tiles.keys().copied());
+        let (tx, ty) = corner(&mut level.tiles.keys().map(|position| self.position(*position)));
+
+        level.tiles.iter().all(|(position, tile)| {
+            let (px, py) = self.position(*position);
+            level.get((px - tx + x, py - ty + y)) == Some(&self.tile(*tile))
+        })
+    }
+}
+
+const ANTI_CLOCKWISE: ((i32, i32), (i32, i32)) = ((0, 1), (1, 0));
+const CLOCKWISE: ((i32, i32), (i32, i32)) = ((0, -1), (1, 0));
+const DOUBLE: ((i32, i32), (i32, i32)) = ((-1, 0), (0, -1));
+const IDENTITY: ((i32, i32), (i32, i32)) = ((1, 0), (0, 1));
+
+/// Only moves the tiles, unlike [`Level::transformed`] starts and rotation blocks keep their
+/// orientation. The shipped levels were authored against this and must not change.
+fn transform(
+    rot: ((i32, i32), (i32, i32)),
+    iter: impl IntoIterator<Item = ((i32, i32), Tile)>,
+) -> impl IntoIterator<Item = ((i32, i32), Tile)> {
+    iter.into_iter()
+        .map(|((x, y), tile)| ((x * rot.0.0 + y * rot.0.1, x * rot.1.0 + y * rot.1.1), tile))
+        .collect::<Vec<_>>()
+}
+
+/// The tile a pictogram character stands for, `Ok(None)` for an empty space.
+pub fn pictogram_tile(c: char) -> Result<Option<Tile>, char> {
+    match c {
+        '⬛' => Ok(None),
+        '🟩' => Ok(Some(Tile::Finish)),
+        '⬜' => Ok(Some(Tile::Wall)),
+        '🟦' => Ok(Some(Tile::Basic)),
+        '🏂' => Ok(Some(Tile::Ice)),
+        '🧑' => Ok(Some(Tile::Start(CWRotation::Zero))),
+        '🧒' => Ok(Some(Tile::Start(CWRotation::Ninety))),
+        '👩' => Ok(Some(Tile::Start(CWRotation::OneEighty))),
+        '🧓' => Ok(Some(Tile::Start(CWRotation::TwoSeventy))),
+        '🔃' => Ok(Some(Tile::CWRot)),
+        '🔄' => Ok(Some(Tile::CCWRot)),
+        other => Err(other),
+    }
+}
+
+pub fn from_pictogram(lines: &[&str]) -> impl IntoIterator<Item = ((i32, i32), Tile)> {
+    let width = lines[0].chars().count() as i32;
+    let length = lines.len() as i32;
+
+    lines
+        .iter()
+        .flat_map(|line| line.chars())
+        .enumerate()
+        .filter_map(|(index, c)| {
+            let index = index as i32;
+            let coords = (index % width - width / 2, index / width - length / 2);
+
+            match pictogram_tile(c) {
+                Ok(tile) => tile.map(|tile| (coords, tile)),
+                Err(other) => {
+                    tracing::warn!(?other, "unrecognised pictogram");
+                    None
+                }
+            }
+        })
+        .collect::<Vec<_>>()
+}
@@ -0,0 +1,171 @@
+use std::fmt;
+
+use crate::{
+    actions::Action,
+    level::{Level, LevelBuilder, Tile, from_pictogram, pictogram_tile},
+};
+
+/// Parses a level written as a few `key: value` lines followed by its pictogram, e.g.
+///
+/// ```text
+/// # Comments start with a hash
+/// name: Crossing
+/// action_limit: 6
+/// actions: FRBL
+/// commands: 1
+/// steps: 3
+/// waste: 11
+/// zoom: 1.2
+/// 🧑🟦🟦🟩
+/// ⬛⬛⬛⬛
+/// 🟩🟦🟦👩
+/// ```
+///
+/// Only the pictogram is required, `actions` defaults to all four and the challenges are optional.
+/// The name is leaked to get the `&'static str` levels use, these are loaded once by tools.
+pub fn parse(text: &str) -> Result<Level, LevelFileError> {
+    let mut builder = LevelBuilder::new();
+    let mut rows = Vec::new();
+
+    for line in text.lines().map(str::trim) {
+        if line.is_empty() || line.starts_with('#') {
+            continue;
+        }
+
+        let Some((key, value)) = line.split_once(':') else {
+            for c in line.chars() {
+                pictogram_tile(c).map_err(LevelFileError::UnknownTile)?;
+            }
+
+            rows.push(line);
+            continue;
+        };
+
+        let (key, value) = (key.trim(), value.trim());
+        let invalid = || LevelFileError::InvalidNumber {
+            key: key.into(),
+            value: value.into(),
+        };
+        let number = || value.parse::<usize>().map_err(|_| invalid());
+
+        builder = match key {
+            "name" => {
+                let name: &'static str = Box::leak(value.to_string().into_boxed_str());
+                builder.id(name).name(name)
+            }
+            "action_limit" => builder.action_limit(number()?),
+            "actions" => builder.actions(
+                value
+                    .chars()
+                    .filter(|c| !c.is_whitespace())
+                    .map(|c| Action::from_code(c).ok_or(LevelFileError::InvalidAction(c)))
+                    .collect::<Result<Vec<_>, _>>()?,
+            ),
+            "commands" => builder.command_challenge(number()?),
+            "steps" => builder.step_challenge(number()?),
+            "waste" => builder.waste_challenge(number()?),
+            "zoom" => builder.zoom(value.parse().map_err(|_| invalid())?),
+            other => return Err(LevelFileError::UnknownKey(other.into())),
+        };
+    }
+
+    let Some(width) = rows.first().map(|row| row.chars().count()) else {
+        return Err(LevelFileError::NoTiles);
+    };
+
+    if let Some(row) = rows.iter().position(|row| row.chars().count() != width) {
+        return Err(LevelFileError::RaggedRow(row + 1));
+    }
+
+    let level = builder.insert(from_pictogram(&rows)).build();
+
+    if !level
+        .tiles
+        .values()
+        .any(|tile| matches!(tile, Tile::Start(_)))
+    {
+        return Err(LevelFileError::NoStart);
+    }
+
+    Ok(level)
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum LevelFileError {
+    UnknownKey(String),
+    InvalidNumber {
+        key: String,
+        value: String,
+    },
+    InvalidAction(char),
+    UnknownTile(char),
+    /// The 1-based pictogram row that isn't as wide as the first.
+    RaggedRow(usize),
+    NoTiles,
+    NoStart,
+}
+
+impl fmt::Display for LevelFileError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            LevelFileError::UnknownKey(key) => write!(f, "'{key}' isn't a level setting"),
+            LevelFileError::InvalidNumber { key, value } => {
+                write!(f, "{key} should be a number, not '{value}'")
+            }
+            LevelFileError::InvalidAction(c) => write!(f, "'{c}' isn't a command"),
+            LevelFileError::UnknownTile(c) => write!(f, "'{c}' isn't a tile"),
+            LevelFileError::RaggedRow(row) => {
+                write!(
+                    f,
+                    "Row {row} of the pictogram is a different width to the first"
+                )
+            }
+            LevelFileError::NoTiles => write!(f, "The level has no pictogram"),
+            LevelFileError::NoStart => write!(f, "The level has nowhere to start"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use Action::*;
+
+    #[test]
+    fn parses_a_level() {
+        let level = parse(
+            "# A comment
+            name: Crossing
+            action_limit: 6
+            actions: FR BL
+            commands: 4
+            zoom: 1.5
+            🧑🟦🟦🟩
+            ⬛⬛⬛⬛
+            🟩🟦🟦👩",
+        )
+        .unwrap();
+
+        assert_eq!(level.name, "Crossing");
+        assert_eq!(level.action_limit, 6);
+        assert_eq!(level.actions, vec![Forward, Right, Backward, Left]);
+        assert_eq!(level.command_challenge(), Some(4));
+        assert_eq!(level.step_challenge(), None);
+        assert_eq!(level.zoom, Some(1.5));
+        assert_eq!(level.tiles.len(), 8);
+        assert_eq!(level.get((-2, -1)), Some(&Tile::DEFAULT_START));
+
+        assert_eq!(parse("🧑🟦\n🟦").unwrap_err(), LevelFileError::RaggedRow(2));
+        assert_eq!(
+            parse("🧑🟦X").unwrap_err(),
+            LevelFileError::UnknownTile('X')
+        );
+        assert_eq!(parse("🟦🟩").unwrap_err(), LevelFileError::NoStart);
+        assert_eq!(parse("name: Empty").unwrap_err(), LevelFileError::NoTiles);
+        assert_eq!(
+            parse("limit: 3\n🧑").unwrap_err(),
+            LevelFileError::UnknownKey("limit".into())
+        );
+    }
+}
@@ -0,0 +1,14 @@
+//! The rules of Simon Says without the engine: actions, levels, the simulation, challenges, the
+//! solver and the level and share code formats. The `bevy` feature derives the ECS traits the game
+//! needs on these types.
+
+#![feature(stmt_expr_attributes)]
+
+pub mod actions;
+pub mod challenge;
+pub mod level;
+pub mod level_file;
+pub mod player;
+pub mod share_code;
+pub mod simulation;
+pub mod solver;
@@ -0,0 +1,8 @@
+use crate::actions::CWRotation;
+
+#[derive(Debug, Default, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "bevy", derive(bevy::prelude::Component))]
+pub struct Player {
+    pub position: (i32, i32),
+    pub rotation: CWRotation,
+}
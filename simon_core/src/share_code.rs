@@ -0,0 +1,152 @@
+use std::fmt;
+
+use crate::{
+    actions::{Action, ActionPlan},
+    level::Level,
+};
+
+/// A solution bundled with the name of the level it solves, written as `Crucible:FRRBL`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ShareCode {
+    pub level: String,
+    pub plan: ActionPlan,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ShareCodeError {
+    MissingSeparator,
+    InvalidAction(char),
+    WrongLevel { expected: String, found: String },
+    ActionNotAllowed(Action),
+    TooManyActions { limit: usize, found: usize },
+}
+
+impl fmt::Display for ShareCodeError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            ShareCodeError::MissingSeparator => write!(f, "Codes look like Level:FRBL"),
+            ShareCodeError::InvalidAction(c) => write!(f, "'{c}' isn't a command"),
+            ShareCodeError::WrongLevel { expected, found } => {
+                write!(f, "This code is for {found}, not {expected}")
+            }
+            ShareCodeError::ActionNotAllowed(action) => {
+                write!(f, "{} isn't available on this level", String::from(*action))
+            }
+            ShareCodeError::TooManyActions { limit, found } => {
+                write!(f, "{found} commands is over the limit of {limit}")
+            }
+        }
+    }
+}
+
+impl fmt::Display for ShareCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}:{}", self.level, self.plan.to_code())
+    }
+}
+
+impl ShareCode {
+    pub fn new(level: &Level, plan: &ActionPlan) -> Self {
+        ShareCode {
+            level: level.name.into(),
+            plan: plan.clone(),
+        }
+    }
+
+    pub fn parse(code: &str) -> Result<Self, ShareCodeError> {
+        let (level, plan) = code
+            .trim()
+            .rsplit_once(':')
+            .ok_or(ShareCodeError::MissingSeparator)?;
+
+        Ok(ShareCode {
+            level: level.trim().into(),
+            plan: ActionPlan::from_code(plan).map_err(ShareCodeError::InvalidAction)?,
+        })
+    }
+
+    /// Checks the code can be played on `level`, level names are compared case insensitively.
+    pub fn validate(&self, level: &Level) -> Result<(), ShareCodeError> {
+        if !self.level.eq_ignore_ascii_case(level.name) {
+            return Err(ShareCodeError::WrongLevel {
+                expected: level.name.into(),
+                found: self.level.clone(),
+            });
+        }
+
+        if let Some(action) = self
+            .plan
+            .iter()
+            .find(|action| !level.actions.contains(action))
+        {
+            return Err(ShareCodeError::ActionNotAllowed(*action));
+        }
+
+        if self.plan.len() > level.action_limit {
+            return Err(ShareCodeError::TooManyActions {
+                limit: level.action_limit,
+                found: self.plan.len(),
+            });
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    use Action::*;
+
+    fn level() -> Level {
+        Level::builder()
+            .name("Crucible")
+            .actions([Forward, Right, Left])
+            .action_limit(4)
+            .build()
+    }
+
+    #[test]
+    fn round_trip() {
+        let code = ShareCode::new(&level(), &ActionPlan(vec![Forward, Right, Right, Left]));
+
+        assert_eq!(code.to_string(), "Crucible:FRRL");
+        assert_eq!(ShareCode::parse("Crucible:FRRL"), Ok(code.clone()));
+        assert_eq!(
+            ShareCode::parse("  crucible : frrl "),
+            Ok(ShareCode {
+                level: "crucible".into(),
+                ..code
+            })
+        );
+    }
+
+    #[test]
+    fn validation() {
+        let level = level();
+        let validate = |code: &str| ShareCode::parse(code).and_then(|code| code.validate(&level));
+
+        assert_eq!(validate("crucible:FRL"), Ok(()));
+        assert_eq!(validate("FRL"), Err(ShareCodeError::MissingSeparator));
+        assert_eq!(
+            validate("Crucible:FXL"),
+            Err(ShareCodeError::InvalidAction('X'))
+        );
+        assert_eq!(
+            validate("Rift:FRL"),
+            Err(ShareCodeError::WrongLevel {
+                expected: "Crucible".into(),
+                found: "Rift".into()
+            })
+        );
+        assert_eq!(
+            validate("Crucible:FBL"),
+            Err(ShareCodeError::ActionNotAllowed(Backward))
+        );
+        assert_eq!(
+            validate("Crucible:FRLFR"),
+            Err(ShareCodeError::TooManyActions { limit: 4, found: 5 })
+        );
+    }
+}
@@ -0,0 +1,896 @@
+//! Exhaustive search over every plan a level allows, used to check levels and their challenges and
+//! to measure how hard they are.
+
+use std::{
+    cmp::Ordering,
+    collections::{BTreeMap, HashMap, HashSet, VecDeque},
+    fmt,
+};
+
+use itertools::Itertools;
+use rayon::prelude::*;
+use serde::Serialize;
+
+use crate::{
+    actions::{Action, ActionPlan},
+    level::{Level, Symmetry, Tile},
+    player::Player,
+    simulation::{SimulationEvent, run_simulation_step},
+};
+
+#[derive(Debug, Clone, PartialEq)]
+pub struct Solution {
+    pub path: Vec<Action>,
+    pub solution_size: usize,
+    pub steps: usize,
+}
+
+fn action_iter(action_list: &[Action]) -> impl Iterator<Item = Action> + Clone {
+    action_list.iter().copied()
+}
+
+/// Every plan up to the level's action limit, shortest first.
+pub fn solution_iter(level: &Level) -> impl Iterator<Item = Vec<Action>> {
+    (1..=level.action_limit).flat_map(move |depth| {
+        (1..=depth)
+            .map(|_| action_iter(&level.actions))
+            .multi_cartesian_product()
+    })
+}
+
+/// Every plan within the level's action limit that reaches the finish, shortest plans first and
+/// then in the order of `level.actions`.
+///
+/// Plans are walked as a tree of prefixes so the first pass through a plan is only simulated once
+/// for everything that starts with it, and the top of the tree is searched in parallel. When the
+/// level maps onto itself under a [`Symmetry`], only the smallest plan of each set of transformed
+/// plans is simulated and the rest are filled in from its result.
+///
+/// Nothing is simulated per plan though, every state a player can reach is found up front with a
+/// [`StateSpace`] search and each plan only follows its moves, passes after the first included.
+pub fn enumerate_solutions(level: &Level) -> Vec<Solution> {
+    let search = Search::new(level);
+    let root = Prefix {
+        plan: Vec::new(),
+        players: search.space.start.clone(),
+        orders: vec![Ordering::Equal; search.symmetries.len()],
+        finished: None,
+    };
+
+    let mut solutions = BTreeMap::new();
+
+    for (plan, steps) in search.search(&root) {
+        for symmetry in &search.symmetries {
+            let image = plan
+                .iter()
+                .map(|index| symmetry[*index])
+                .collect::<Vec<_>>();
+            solutions.insert((image.len(), image), steps);
+        }
+
+        solutions.insert((plan.len(), plan), steps);
+    }
+
+    solutions
+        .into_iter()
+        .map(|((solution_size, plan), steps)| Solution {
+            path: plan.into_iter().map(|index| level.actions[index]).collect(),
+            solution_size,
+            steps,
+        })
+        .collect()
+}
+
+/// Prefixes shorter than this have their children searched in parallel.
+const PARALLEL_DEPTH: usize = 2;
+
+/// A player's position and rotation, as an index into [`StateSpace::moves`].
+type PlayerState = u32;
+
+#[derive(Debug, Clone, Copy)]
+struct Move {
+    to: PlayerState,
+    /// Whether the player is on the finish afterwards.
+    finished: bool,
+}
+
+/// Every state the players can reach, found with a breadth first search from the start tiles.
+/// Players never affect each other, so each one's moves are worked out once, shared by every plan
+/// and every player.
+#[derive(Debug)]
+struct StateSpace {
+    /// The move each of `level.actions` makes from each state, `None` if the player dies.
+    moves: Vec<Vec<Option<Move>>>,
+    start: Vec<PlayerState>,
+}
+
+impl StateSpace {
+    fn new(level: &Level) -> Self {
+        let mut states = Vec::new();
+        let mut ids = HashMap::new();
+        let mut queue = VecDeque::new();
+
+        let mut intern = |player: Player, queue: &mut VecDeque<Player>| {
+            *ids.entry(player).or_insert_with(|| {
+                states.push(player);
+                queue.push_back(player);
+                (states.len() - 1) as PlayerState
+            })
+        };
+
+        let start = level
+            .tiles
+            .iter()
+            .filter(|(_, tile)| matches!(tile, Tile::Start(_)))
+            .map(|(position, tile)| {
+                let player = Player {
+                    position: *position,
+                    rotation: tile.rotation(),
+                };
+                intern(player, &mut queue)
+            })
+            .collect();
+
+        // States are numbered in the order they're queued, so their moves are too
+        let mut moves = Vec::new();
+
+        while let Some(player) = queue.pop_front() {
+            let mut player_moves = Vec::with_capacity(level.actions.len());
+
+            for action in &level.actions {
+                let (next, event) = run_simulation_step(level, &[player], *action)[0];
+
+                player_moves.push(match event {
+                    Some(SimulationEvent::Died(_)) => None,
+                    _ => Some(Move {
+                        to: intern(next, &mut queue),
+                        finished: event == Some(SimulationEvent::Finished),
+                    }),
+                });
+            }
+
+            moves.push(player_moves);
+        }
+
+        StateSpace { moves, start }
+    }
+
+    /// Moves every player with `level.actions[action]`, `None` if any of them dies. The flag says
+    /// whether they've all finished.
+    fn step(&self, players: &[PlayerState], action: usize) -> Option<(Vec<PlayerState>, bool)> {
+        let mut finished = true;
+        let players = players
+            .iter()
+            .map(|player| {
+                let Move { to, finished: done } = self.moves[*player as usize][action]?;
+                finished &= done;
+                Some(to)
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        Some((players, finished))
+    }
+}
+
+#[derive(Debug, Clone)]
+struct Prefix {
+    /// Indices into `level.actions`.
+    plan: Vec<usize>,
+    /// The players after the first pass.
+    players: Vec<PlayerState>,
+    /// How each symmetric copy of the plan compares with it so far.
+    orders: Vec<Ordering>,
+    /// The step the first pass finished on, if it did before the end of the plan.
+    finished: Option<usize>,
+}
+
+struct Search<'a> {
+    level: &'a Level,
+    space: StateSpace,
+    /// The non-identity symmetries of the level, as permutations of `level.actions`.
+    symmetries: Vec<Vec<usize>>,
+}
+
+impl<'a> Search<'a> {
+    fn new(level: &'a Level) -> Self {
+        Search {
+            level,
+            space: StateSpace::new(level),
+            symmetries: Symmetry::ALL
+                .into_iter()
+                .filter(|symmetry| *symmetry != Symmetry::IDENTITY)
+                .filter_map(|symmetry| symmetry.permutation(level))
+                .collect(),
+        }
+    }
+
+    fn search(&self, prefix: &Prefix) -> Vec<(Vec<usize>, usize)> {
+        let mut solutions = Vec::new();
+
+        if prefix.plan.len() < PARALLEL_DEPTH {
+            solutions.extend(self.evaluate(prefix));
+
+            let children = self.children(prefix).collect::<Vec<_>>();
+            solutions.par_extend(
+                children
+                    .par_iter()
+                    .flat_map_iter(|child| self.search(child)),
+            );
+        } else {
+            self.search_into(prefix, &mut solutions);
+        }
+
+        solutions
+    }
+
+    fn search_into(&self, prefix: &Prefix, solutions: &mut Vec<(Vec<usize>, usize)>) {
+        solutions.extend(self.evaluate(prefix));
+
+        for child in self.children(prefix) {
+            self.search_into(&child, solutions);
+        }
+    }
+
+    fn children<'p>(&'p self, prefix: &'p Prefix) -> impl Iterator<Item = Prefix> + 'p {
+        let indices = if prefix.plan.len() < self.level.action_limit {
+            0..self.level.actions.len()
+        } else {
+            0..0
+        };
+
+        indices.filter_map(move |index| self.extend(prefix, index))
+    }
+
+    /// `prefix` followed by `level.actions[index]`, `None` if nothing starting with that can be a
+    /// solution we need to simulate.
+    fn extend(&self, prefix: &Prefix, index: usize) -> Option<Prefix> {
+        let mut orders = prefix.orders.clone();
+
+        for (order, symmetry) in orders.iter_mut().zip(&self.symmetries) {
+            if *order == Ordering::Equal {
+                *order = symmetry[index].cmp(&index);
+            }
+
+            // A transformed copy of this plan comes first, its solutions stand in for ours
+            if *order == Ordering::Less {
+                return None;
+            }
+        }
+
+        let mut plan = prefix.plan.clone();
+        plan.push(index);
+
+        if prefix.finished.is_some() {
+            return Some(Prefix {
+                plan,
+                players: Vec::new(),
+                orders,
+                finished: prefix.finished,
+            });
+        }
+
+        let (players, finished) = self.space.step(&prefix.players, index)?;
+        let finished = finished.then_some(plan.len());
+
+        Some(Prefix {
+            plan,
+            players,
+            orders,
+            finished,
+        })
+    }
+
+    /// The number of steps `prefix` takes to finish when it's the whole plan.
+    fn evaluate(&self, prefix: &Prefix) -> Option<(Vec<usize>, usize)> {
+        if prefix.plan.is_empty() {
+            return None;
+        }
+
+        let steps = match prefix.finished {
+            Some(steps) => steps,
+            None => match self.finish_simulation(prefix) {
+                PlanOutcome::Finished(steps) => steps,
+                PlanOutcome::Died | PlanOutcome::Looped => return None,
+            },
+        };
+
+        Some((prefix.plan.clone(), steps))
+    }
+
+    /// Keeps looping the plan after its first pass until it finishes, dies or repeats itself. The
+    /// simulation is deterministic so it only needs checking for repeats between passes.
+    fn finish_simulation(&self, prefix: &Prefix) -> PlanOutcome {
+        let mut players = prefix.players.clone();
+        let mut previous_passes = HashSet::new();
+        let mut step_count = prefix.plan.len();
+
+        while previous_passes.insert(players.clone()) {
+            for action in &prefix.plan {
+                step_count += 1;
+
+                match self.space.step(&players, *action) {
+                    Some((_, true)) => return PlanOutcome::Finished(step_count),
+                    Some((next, false)) => players = next,
+                    None => return PlanOutcome::Died,
+                }
+            }
+        }
+
+        PlanOutcome::Looped
+    }
+
+    /// Walks every prefix without skipping symmetric copies, so `extend` only rejects a child
+    /// when its first pass dies.
+    fn count_outcomes(&self, prefix: &Prefix, counts: &mut OutcomeCounts) {
+        if !prefix.plan.is_empty() {
+            counts.plans += 1;
+
+            let outcome = match prefix.finished {
+                Some(steps) => PlanOutcome::Finished(steps),
+                None => self.finish_simulation(prefix),
+            };
+
+            match outcome {
+                PlanOutcome::Finished(_) => {}
+                PlanOutcome::Died => counts.died += 1,
+                PlanOutcome::Looped => counts.looped += 1,
+            }
+        }
+
+        if prefix.plan.len() == self.level.action_limit {
+            return;
+        }
+
+        if prefix.finished.is_none() {
+            counts.open_prefixes += 1;
+        }
+
+        for index in 0..self.level.actions.len() {
+            match self.extend(prefix, index) {
+                Some(child) => {
+                    if prefix.finished.is_none() {
+                        counts.surviving_children += 1;
+                    }
+
+                    self.count_outcomes(&child, counts);
+                }
+                None => {
+                    // Everything starting with the child dies on its first pass
+                    let below = plan_count(
+                        self.level.actions.len(),
+                        self.level.action_limit - prefix.plan.len() - 1,
+                    ) + 1;
+                    counts.plans += below;
+                    counts.died += below;
+                }
+            }
+        }
+    }
+}
+
+/// How a plan ends when it's looped until something happens.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+enum PlanOutcome {
+    Finished(usize),
+    Died,
+    Looped,
+}
+
+#[derive(Debug, Default)]
+struct OutcomeCounts {
+    plans: u64,
+    died: u64,
+    looped: u64,
+    /// Prefixes shorter than the limit that haven't finished or died on their first pass.
+    open_prefixes: u64,
+    /// Children of those prefixes that survive their first pass.
+    surviving_children: u64,
+}
+
+/// The number of non-empty plans of up to `limit` commands chosen from `actions` commands.
+fn plan_count(actions: usize, limit: usize) -> u64 {
+    (1..=limit as u32)
+        .map(|len| (actions as u64).pow(len))
+        .sum()
+}
+
+impl Symmetry {
+    /// Where each of `level.actions` goes, if the level and its set of actions are both preserved.
+    fn permutation(self, level: &Level) -> Option<Vec<usize>> {
+        let permutation = level
+            .actions
+            .iter()
+            .map(|action| {
+                level
+                    .actions
+                    .iter()
+                    .position(|other| *other == self.action(*action))
+            })
+            .collect::<Option<Vec<_>>>()?;
+
+        self.preserves(level).then_some(permutation)
+    }
+}
+
+pub fn smallest_solutions(solutions: &[Solution]) -> Vec<Solution> {
+    let Some(minimum_size) = solutions
+        .iter()
+        .map(|solution| solution.solution_size)
+        .min()
+    else {
+        return Vec::new();
+    };
+
+    solutions
+        .iter()
+        .filter(|solution| solution.solution_size == minimum_size)
+        .cloned()
+        .collect()
+}
+
+pub fn fastest_solutions(solutions: &[Solution]) -> Vec<Solution> {
+    let Some(minimum_steps) = solutions.iter().map(|solution| solution.steps).min() else {
+        return Vec::new();
+    };
+
+    solutions
+        .iter()
+        .filter(|solution| solution.steps == minimum_steps)
+        .cloned()
+        .collect()
+}
+
+pub fn slowest_solutions(solutions: &[Solution]) -> Vec<Solution> {
+    let Some(maximum_steps) = solutions.iter().map(|solution| solution.steps).max() else {
+        return Vec::new();
+    };
+
+    solutions
+        .iter()
+        .filter(|solution| solution.steps == maximum_steps)
+        .cloned()
+        .collect()
+}
+
+/// What the solver can tell the player about the plan they've written so far.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct PlanHint {
+    /// Whether the plan already solves the level.
+    pub solves: bool,
+    /// How many commands to remove from the end of the plan before it starts a solution again.
+    pub extra_commands: usize,
+    /// The command after that in the smallest solution that starts the same way.
+    pub next: Option<Action>,
+}
+
+impl PlanHint {
+    /// Whether the plan can still be extended into a solution.
+    pub fn on_track(&self) -> bool {
+        self.extra_commands == 0
+    }
+}
+
+pub fn plan_hint(solutions: &[Solution], plan: &[Action]) -> PlanHint {
+    let valid = (0..=plan.len())
+        .rev()
+        .find(|len| {
+            solutions
+                .iter()
+                .any(|solution| solution.path.starts_with(&plan[..*len]))
+        })
+        .unwrap_or_default();
+
+    PlanHint {
+        solves: solutions.iter().any(|solution| solution.path == plan),
+        extra_commands: plan.len() - valid,
+        next: solutions
+            .iter()
+            .filter(|solution| {
+                solution.path.len() > valid && solution.path.starts_with(&plan[..valid])
+            })
+            .min_by_key(|solution| (solution.solution_size, solution.steps))
+            .map(|solution| solution.path[valid]),
+    }
+}
+
+/// How many different plans solve the level, counting plans that only differ by rotation, mirroring
+/// or which command comes first as the same.
+pub fn canonical_solution_count(solutions: &[Solution]) -> usize {
+    solutions
+        .iter()
+        .map(|solution| ActionPlan(solution.path.clone()).canonicalize())
+        .collect::<HashSet<_>>()
+        .len()
+}
+
+/// The built in challenges with a numeric threshold the solver can find the optimum for.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThresholdKind {
+    Commands,
+    Steps,
+    Waste,
+}
+
+impl ThresholdKind {
+    pub const ALL: [ThresholdKind; 3] = [
+        ThresholdKind::Commands,
+        ThresholdKind::Steps,
+        ThresholdKind::Waste,
+    ];
+
+    pub fn name(self) -> &'static str {
+        match self {
+            ThresholdKind::Commands => "Parsimony",
+            ThresholdKind::Steps => "Alacrity",
+            ThresholdKind::Waste => "Circuity",
+        }
+    }
+
+    fn threshold(self, level: &Level) -> Option<usize> {
+        match self {
+            ThresholdKind::Commands => level.command_challenge(),
+            ThresholdKind::Steps => level.step_challenge(),
+            ThresholdKind::Waste => level.waste_challenge(),
+        }
+    }
+
+    fn optimum(self, solutions: &[Solution]) -> Option<usize> {
+        match self {
+            ThresholdKind::Commands => solutions
+                .iter()
+                .map(|solution| solution.solution_size)
+                .min(),
+            ThresholdKind::Steps => solutions.iter().map(|solution| solution.steps).min(),
+            ThresholdKind::Waste => solutions.iter().map(|solution| solution.steps).max(),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct ThresholdCheck {
+    pub kind: ThresholdKind,
+    pub threshold: Option<usize>,
+    /// The best any solution does, `None` if the level can't be solved.
+    pub optimum: Option<usize>,
+}
+
+impl ThresholdCheck {
+    pub fn is_achievable(&self) -> bool {
+        match (self.threshold, self.optimum) {
+            (Some(threshold), Some(optimum)) => match self.kind {
+                ThresholdKind::Commands | ThresholdKind::Steps => optimum <= threshold,
+                ThresholdKind::Waste => optimum >= threshold,
+            },
+            _ => false,
+        }
+    }
+
+    pub fn is_optimal(&self) -> bool {
+        self.threshold.is_some() && self.threshold == self.optimum
+    }
+
+    /// Unset thresholds are fine, set ones have to be exactly the optimum.
+    pub fn is_ok(&self) -> bool {
+        self.threshold.is_none() || self.is_optimal()
+    }
+}
+
+impl fmt::Display for ThresholdCheck {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let optimum = match self.optimum {
+            Some(optimum) => optimum.to_string(),
+            None => "-".into(),
+        };
+
+        match self.threshold {
+            None => write!(f, "{:<10} unset  optimum {optimum}", self.kind.name()),
+            Some(threshold) => write!(
+                f,
+                "{:<10} {threshold:<6} optimum {optimum:<6} {}",
+                self.kind.name(),
+                match (self.is_achievable(), self.is_optimal()) {
+                    (_, true) => "ok",
+                    (true, false) => "achievable, not optimal",
+                    (false, _) => "UNACHIEVABLE",
+                }
+            ),
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LevelReport {
+    pub name: &'static str,
+    pub solutions: usize,
+    pub checks: Vec<ThresholdCheck>,
+}
+
+impl LevelReport {
+    pub fn is_ok(&self) -> bool {
+        self.solutions > 0 && self.checks.iter().all(ThresholdCheck::is_ok)
+    }
+}
+
+impl fmt::Display for LevelReport {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        writeln!(f, "{} ({} solutions)", self.name, self.solutions)?;
+
+        for check in &self.checks {
+            writeln!(f, "  {check}")?;
+        }
+
+        Ok(())
+    }
+}
+
+/// Solves `level` and compares its challenge thresholds with the optimum.
+pub fn check_level(level: &Level) -> LevelReport {
+    let solutions = enumerate_solutions(level);
+
+    LevelReport {
+        name: level.name,
+        solutions: solutions.len(),
+        checks: ThresholdKind::ALL
+            .into_iter()
+            .map(|kind| ThresholdCheck {
+                kind,
+                threshold: kind.threshold(level),
+                optimum: kind.optimum(&solutions),
+            })
+            .collect(),
+    }
+}
+
+/// The best value of one measure over a level's solutions, and how far it is from the next best.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+pub struct Extreme {
+    pub best: usize,
+    /// `None` when every solution shares the best value.
+    pub gap: Option<usize>,
+}
+
+impl Extreme {
+    fn new(values: impl Iterator<Item = usize>, reverse: bool) -> Option<Self> {
+        let mut values = values.collect::<Vec<_>>();
+        values.sort_unstable();
+        values.dedup();
+
+        if reverse {
+            values.reverse();
+        }
+
+        Some(Extreme {
+            best: *values.first()?,
+            gap: values.get(1).map(|next| next.abs_diff(values[0])),
+        })
+    }
+}
+
+/// Measures of how hard a level is, taken from every plan within its action limit.
+#[derive(Debug, Clone, Serialize)]
+pub struct LevelStats {
+    pub name: &'static str,
+    /// Every plan within the action limit.
+    pub plans: u64,
+    pub solutions: usize,
+    pub canonical_solutions: usize,
+    /// The average number of commands that keep a plan alive through its first pass, over the
+    /// plans that are still going.
+    pub branching_factor: f64,
+    pub smallest: Option<Extreme>,
+    pub fastest: Option<Extreme>,
+    pub slowest: Option<Extreme>,
+    /// The chance a plan picked at random walks someone off the level.
+    pub death_rate: f64,
+    /// The chance a plan picked at random loops forever without finishing.
+    pub loop_rate: f64,
+}
+
+impl LevelStats {
+    /// How many bits of luck a random plan needs to solve the level, infinite without a solution.
+    pub fn difficulty(&self) -> f64 {
+        (self.plans as f64 / self.solutions as f64).log2()
+    }
+}
+
+impl fmt::Display for LevelStats {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let extreme = |extreme: Option<Extreme>| match extreme {
+            Some(Extreme {
+                best,
+                gap: Some(gap),
+            }) => format!("{best} (+{gap})"),
+            Some(Extreme { best, gap: None }) => format!("{best}"),
+            None => "-".into(),
+        };
+
+        writeln!(
+            f,
+            "{}: difficulty {:.1}, {} of {} plans solve it, {} canonical",
+            self.name,
+            self.difficulty(),
+            self.solutions,
+            self.plans,
+            self.canonical_solutions
+        )?;
+        writeln!(
+            f,
+            "  branching {:.2}, {:.1}% die, {:.1}% loop",
+            self.branching_factor,
+            self.death_rate * 100.,
+            self.loop_rate * 100.
+        )?;
+        writeln!(
+            f,
+            "  smallest {}, fastest {}, slowest {}",
+            extreme(self.smallest),
+            extreme(self.fastest),
+            extreme(self.slowest)
+        )
+    }
+}
+
+/// Solves `level` and measures how the plans within its action limit play out.
+pub fn level_stats(level: &Level) -> LevelStats {
+    let solutions = enumerate_solutions(level);
+    let search = Search {
+        symmetries: Vec::new(),
+        ..Search::new(level)
+    };
+    let root = Prefix {
+        plan: Vec::new(),
+        players: search.space.start.clone(),
+        orders: Vec::new(),
+        finished: None,
+    };
+
+    let mut counts = OutcomeCounts::default();
+    search.count_outcomes(&root, &mut counts);
+
+    LevelStats {
+        name: level.name,
+        plans: counts.plans,
+        solutions: solutions.len(),
+        canonical_solutions: canonical_solution_count(&solutions),
+        branching_factor: counts.surviving_children as f64 / counts.open_prefixes.max(1) as f64,
+        smallest: Extreme::new(
+            solutions.iter().map(|solution| solution.solution_size),
+            false,
+        ),
+        fastest: Extreme::new(solutions.iter().map(|solution| solution.steps), false),
+        slowest: Extreme::new(solutions.iter().map(|solution| solution.steps), true),
+        death_rate: counts.died as f64 / counts.plans.max(1) as f64,
+        loop_rate: counts.looped as f64 / counts.plans.max(1) as f64,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use crate::level::{self, SCENES};
+
+    use super::*;
+
+    #[test]
+    fn challenge_thresholds_are_optimal() {
+        let failures = SCENES
+            .iter()
+            .filter_map(|scene| match scene {
+                level::Scene::Level(level) => Some(check_level(level)),
+                _ => None,
+            })
+            .filter(|report| !report.is_ok())
+            .map(|report| report.to_string())
+            .collect::<String>();
+
+        assert!(failures.is_empty(), "\n{failures}");
+    }
+
+    #[test]
+    fn hints() {
+        use Action::*;
+
+        let solution = |path: Vec<Action>, steps| Solution {
+            solution_size: path.len(),
+            path,
+            steps,
+        };
+        let solutions = [
+            solution(vec![Forward, Right], 6),
+            solution(vec![Left, Left, Forward], 9),
+            solution(vec![Left, Right, Right, Forward], 8),
+        ];
+
+        let hint = plan_hint(&solutions, &[]);
+        assert!(hint.on_track() && !hint.solves);
+        assert_eq!(hint.next, Some(Forward));
+
+        let hint = plan_hint(&solutions, &[Left]);
+        assert!(hint.on_track());
+        assert_eq!(hint.next, Some(Left));
+
+        let hint = plan_hint(&solutions, &[Left, Right, Left, Left]);
+        assert_eq!(hint.extra_commands, 2);
+        assert_eq!(hint.next, Some(Right));
+
+        let hint = plan_hint(&solutions, &[Forward, Right]);
+        assert!(hint.solves);
+        assert_eq!(hint.next, None);
+    }
+
+    #[test]
+    fn pothole_stats() {
+        let stats = SCENES
+            .iter()
+            .find_map(|scene| match scene {
+                level::Scene::Level(level) if level.name == "Pothole" => Some(level_stats(level)),
+                _ => None,
+            })
+            .unwrap();
+
+        assert_eq!(stats.plans, 20);
+        assert_eq!((stats.solutions, stats.canonical_solutions), (1, 1));
+        assert_eq!(stats.death_rate, 17. / 20.);
+        assert_eq!(stats.loop_rate, 2. / 20.);
+        assert_eq!(stats.branching_factor, 8. / 3.);
+        assert_eq!(stats.smallest, Some(Extreme { best: 2, gap: None }));
+        assert_eq!(stats.fastest, Some(Extreme { best: 6, gap: None }));
+    }
+
+    #[test]
+    fn transformed_levels_keep_their_solutions() {
+        for name in ["Support", "Swirl"] {
+            let level = SCENES
+                .iter()
+                .find_map(|scene| match scene {
+                    level::Scene::Level(level) if level.name == name => Some(level),
+                    _ => None,
+                })
+                .unwrap();
+            let solutions = enumerate_solutions(level);
+            assert!(!solutions.is_empty());
+
+            for symmetry in Symmetry::ALL {
+                let expected = solutions
+                    .iter()
+                    .map(|solution| Solution {
+                        path: solution
+                            .path
+                            .iter()
+                            .map(|action| symmetry.action(*action))
+                            .collect(),
+                        ..solution.clone()
+                    })
+                    .collect::<Vec<_>>();
+
+                assert_eq!(
+                    enumerate_solutions(&level.transformed(symmetry)),
+                    expected,
+                    "{name} under {symmetry:?}"
+                );
+            }
+        }
+    }
+
+    #[test]
+    fn level_symmetries() {
+        let symmetries = |name: &str| {
+            SCENES
+                .iter()
+                .find_map(|scene| match scene {
+                    level::Scene::Level(level) if level.name == name => Some(level),
+                    _ => None,
+                })
+                .map(|level| {
+                    Symmetry::ALL
+                        .into_iter()
+                        .filter(|symmetry| symmetry.preserves(level))
+                        .count()
+                })
+                .unwrap()
+        };
+
+        // Mirrored top to bottom
+        assert_eq!(symmetries("Convergence"), 2);
+        assert_eq!(symmetries("Crucible"), 1);
+    }
+}